@@ -0,0 +1,243 @@
+use crate::{ExplodingDie, Probability, ProbabilityCube};
+use std::collections::HashMap;
+
+/// Whether a check is rolled once, or twice keeping the better or worse total.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum RollMode {
+    /// The die is rolled once.
+    #[default]
+    Normal,
+
+    /// The die is rolled twice, following every explosion each time, and the higher total is kept.
+    Advantage,
+
+    /// The die is rolled twice, following every explosion each time, and the lower total is kept.
+    Disadvantage,
+}
+
+impl std::fmt::Display for RollMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RollMode::Normal => write!(f, "normal"),
+            RollMode::Advantage => write!(f, "advantage"),
+            RollMode::Disadvantage => write!(f, "disadvantage"),
+        }
+    }
+}
+
+/// Computes the probability of beating a given difficulty class when rolling with advantage or
+/// disadvantage, spending turbo tokens optimally.
+///
+/// The two rolls are made one after the other, and share the player's turbo tokens. With
+/// advantage, the check succeeds if either roll beats the DC, so the second roll is only needed if
+/// the first fails; tokens spent on the first roll are then no longer available for the second.
+/// With disadvantage, both rolls must beat the DC. Whenever a die could use turbo tokens on the
+/// first roll, the player spends them only if doing so makes the check more likely to succeed.
+/// The second roll spends tokens as described in
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens),
+/// which is optimal for the last roll of a check.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `dc` - The difficulty class to beat.
+/// * `mode` - Whether to roll with advantage or disadvantage.
+pub fn probability_of_success_with_advantage(
+    die: impl ExplodingDie,
+    turbo_tokens: u32,
+    dc: u32,
+    mode: RollMode,
+) -> f64 {
+    probability_of_success_with_advantage_in(die, turbo_tokens, dc, mode)
+}
+
+/// Same as [`probability_of_success_with_advantage`], but carries out the calculation in the given
+/// number type.
+pub fn probability_of_success_with_advantage_in<P: Probability, D: ExplodingDie>(
+    die: D,
+    turbo_tokens: u32,
+    dc: u32,
+    mode: RollMode,
+) -> P {
+    ProbabilityCube::<P, D>::new(&[die], turbo_tokens, dc)
+        .probability_with_advantage(die, turbo_tokens, dc, mode)
+        .expect("probability should be in the cube")
+}
+
+/// Computes the probability of beating a DC with advantage or disadvantage, looking up the
+/// probability of the second roll, or of a normal roll, with `single`.
+///
+/// Returns [`None`] if any of the lookups do.
+pub(crate) fn with_advantage<P: Probability, D: ExplodingDie>(
+    die: D,
+    turbo_tokens: u32,
+    dc: u32,
+    mode: RollMode,
+    single: impl Fn(D, u32, u32) -> Option<P>,
+) -> Option<P> {
+    if mode == RollMode::Normal {
+        return single(die, turbo_tokens, dc);
+    }
+
+    let mut first = FirstRoll {
+        start: die,
+        dc,
+        mode,
+        single,
+        memo: HashMap::new(),
+    };
+    first.value(die, turbo_tokens, dc)
+}
+
+/// The first of the two rolls made with advantage or disadvantage.
+struct FirstRoll<P, D, F> {
+    /// The type of die both rolls start with.
+    start: D,
+
+    /// The difficulty class of the check.
+    dc: u32,
+
+    /// Whether the check is rolled with advantage or disadvantage.
+    mode: RollMode,
+
+    /// Looks up the probability of the second roll beating the DC.
+    single: F,
+
+    /// Memoized probabilities of the check succeeding, keyed by the die being rolled in the first
+    /// roll, the number of tokens held, and the remaining DC.
+    memo: HashMap<(D, u32, u32), P>,
+}
+
+impl<P, D, F> FirstRoll<P, D, F>
+where
+    P: Probability,
+    D: ExplodingDie,
+    F: Fn(D, u32, u32) -> Option<P>,
+{
+    /// Returns the probability of the check succeeding once the first roll succeeds with the given
+    /// number of tokens left over.
+    fn success(&self, turbo_tokens: u32) -> Option<P> {
+        match self.mode {
+            RollMode::Disadvantage => (self.single)(self.start, turbo_tokens, self.dc),
+            _ => Some(P::one()),
+        }
+    }
+
+    /// Returns the probability of the check succeeding once the first roll fails with the given
+    /// number of tokens left over.
+    fn failure(&self, turbo_tokens: u32) -> Option<P> {
+        match self.mode {
+            RollMode::Advantage => (self.single)(self.start, turbo_tokens, self.dc),
+            _ => Some(P::zero()),
+        }
+    }
+
+    /// Returns the probability of the check succeeding when rolling `die` in the middle of the
+    /// first roll, with `dc` left to beat.
+    fn value(&mut self, die: D, turbo_tokens: u32, dc: u32) -> Option<P> {
+        if dc <= 1 {
+            return self.success(turbo_tokens);
+        }
+
+        let key = (die, turbo_tokens, dc);
+        if let Some(p) = self.memo.get(&key) {
+            return Some(p.clone());
+        }
+
+        let sides = die.sides();
+        let p = (1..=sides)
+            .map(|roll| Some(self.best(die, turbo_tokens, dc, roll)? / P::ratio(sides, 1)))
+            .sum::<Option<P>>()?;
+        self.memo.insert(key, p.clone());
+        Some(p)
+    }
+
+    /// Returns the probability of the check succeeding after rolling `roll` on `die` and then
+    /// either keeping the roll or spending turbo tokens on it, whichever is better. Ties are broken
+    /// in favor of keeping the roll.
+    fn best(&mut self, die: D, turbo_tokens: u32, dc: u32, roll: u32) -> Option<P> {
+        let sides = die.sides();
+
+        // The roll beats the DC on its own.
+        if roll >= dc {
+            return self.success(turbo_tokens);
+        }
+
+        // The die explodes on its own.
+        let next = die.explode();
+        if let Some(next) = next.filter(|_| roll == sides) {
+            return self.value(next, turbo_tokens, dc - sides);
+        }
+
+        let keep = self.failure(turbo_tokens)?;
+        let spend = match next {
+            // Spend tokens to explode the die, if the DC is out of reach without exploding...
+            Some(next) if dc > sides => match sides - roll {
+                needed if needed <= turbo_tokens => {
+                    Some(self.value(next, turbo_tokens - needed, dc - sides)?)
+                },
+                _ => None,
+            },
+            // ...or spend tokens to reach the DC.
+            _ => match dc - roll {
+                needed if needed <= turbo_tokens => Some(self.success(turbo_tokens - needed)?),
+                _ => None,
+            },
+        };
+
+        Some(match spend {
+            Some(spend) if spend > keep => spend,
+            _ => keep,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{sweep, Case, MAX_DC, MAX_TOKENS},
+        Die,
+        Exact,
+    };
+
+    #[test]
+    fn matches_independent_rolls_without_tokens() {
+        let cube = ProbabilityCube::<Exact>::new(&Die::ALL, 0, MAX_DC);
+        for case @ Case { die, dc, .. } in sweep().filter(|case| case.turbo_tokens == 0) {
+            let p = cube.probability(die, 0, dc).unwrap().clone();
+            let q = Exact::one() - p.clone();
+            let advantage = cube.probability_with_advantage(die, 0, dc, RollMode::Advantage);
+            let disadvantage = cube.probability_with_advantage(die, 0, dc, RollMode::Disadvantage);
+            assert_eq!(advantage, Some(Exact::one() - q.clone() * q), "{}", case);
+            assert_eq!(disadvantage, Some(p.clone() * p), "{}", case);
+        }
+    }
+
+    #[test]
+    fn advantage_beats_normal_beats_disadvantage() {
+        let cube = ProbabilityCube::<f64>::new(&Die::ALL, MAX_TOKENS, MAX_DC);
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            let normal = *cube.probability(die, turbo_tokens, dc).unwrap();
+            let advantage = cube
+                .probability_with_advantage(die, turbo_tokens, dc, RollMode::Advantage)
+                .unwrap();
+            let disadvantage = cube
+                .probability_with_advantage(die, turbo_tokens, dc, RollMode::Disadvantage)
+                .unwrap();
+            assert!(advantage >= normal - 1e-12, "{}", case);
+            assert!(normal >= disadvantage - 1e-12, "{}", case);
+        }
+    }
+
+    #[test]
+    fn spends_tokens_on_the_first_roll() {
+        // A d4 needs to explode to beat DC 5. A 4 explodes on its own, and a 3 is better off
+        // spending the token to explode than keeping it for the second roll, which would only
+        // succeed half the time. A 1 or 2 leaves the token for the second roll, which explodes
+        // on a 3 or 4: 1/4 + 1/4 + 1/2 * 1/2 = 3/4.
+        let p: Exact = probability_of_success_with_advantage_in(Die::D4, 1, 5, RollMode::Advantage);
+        assert_eq!(p, Exact::ratio(3, 4));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{distribution, Distribution, ExplodingDie, Probability, TAIL_TOLERANCE};
+
+/// The probabilities of each outcome of a contest, where two sides each roll a die and the higher
+/// total wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contest<P = f64> {
+    /// The probability that the first side rolls a higher total.
+    pub win: P,
+
+    /// The probability that both sides roll the same total.
+    pub tie: P,
+
+    /// The probability that the second side rolls a higher total.
+    pub loss: P,
+}
+
+impl<P: Probability> Contest<P> {
+    /// Computes the outcome of a contest from the distribution of each side's total.
+    ///
+    /// Totals in the tail of one distribution are higher than every total stored in the other,
+    /// so only the case where both totals land in the tails is unknown. The three probabilities
+    /// therefore fall short of summing to 1 by the product of the two tails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distributions have different cutoffs.
+    pub fn of(first: &Distribution<P>, second: &Distribution<P>) -> Self {
+        assert_eq!(first.cutoff(), second.cutoff(), "distributions must have the same cutoff");
+
+        let second_stored = second.iter().map(|(_, p)| p.clone()).sum::<P>();
+
+        // The probability that the second side's total is lower than the current total.
+        let mut below = P::zero();
+        let mut contest = Contest {
+            win: P::zero(),
+            tie: P::zero(),
+            loss: P::zero(),
+        };
+        for ((_, p), (_, q)) in first.iter().zip(second.iter()) {
+            let above = second_stored.clone() - below.clone() - q.clone() + second.tail().clone();
+            contest.win = contest.win + p.clone() * below.clone();
+            contest.tie = contest.tie + p.clone() * q.clone();
+            contest.loss = contest.loss + p.clone() * above;
+            below = below + q.clone();
+        }
+
+        contest.win = contest.win + first.tail().clone() * second_stored;
+        contest
+    }
+}
+
+/// Computes the probabilities of each outcome of a contest between two dice, each with their own
+/// turbo tokens.
+///
+/// Each side spends turbo tokens as described in [`distribution`](crate::distribution), aiming for
+/// the highest total. The distributions are computed with a cutoff high enough to leave at most
+/// [`TAIL_TOLERANCE`] probability in each tail.
+///
+/// # Arguments
+///
+/// * `die` - The type of die rolled by the first side.
+/// * `turbo_tokens` - The number of turbo tokens available to the first side.
+/// * `opponent` - The type of die rolled by the second side.
+/// * `opponent_turbo_tokens` - The number of turbo tokens available to the second side.
+pub fn contest(
+    die: impl ExplodingDie,
+    turbo_tokens: u32,
+    opponent: impl ExplodingDie,
+    opponent_turbo_tokens: u32,
+) -> Contest {
+    let mut cutoff = 64;
+    loop {
+        let first = distribution(die, turbo_tokens, cutoff);
+        let second = distribution(opponent, opponent_turbo_tokens, cutoff);
+        if *first.tail() <= TAIL_TOLERANCE && *second.tail() <= TAIL_TOLERANCE {
+            return Contest::of(&first, &second);
+        }
+        cutoff *= 2;
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{
+    advantage::with_advantage,
+    modifier::with_modifier,
+    Die,
+    ExplodingDie,
+    Modifier,
+    Probability,
+    RollMode,
+};
+use std::collections::HashMap;
+
+/// The probability of beating every DC up to a maximum, for every number of turbo tokens up to a
+/// maximum, for a set of dice.
+///
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens)
+/// recurses through every possible roll without remembering any results, so computing a large
+/// table with it solves the same subproblems many times over. The cube instead computes each
+/// (die, turbo tokens, DC) entry exactly once, in order of increasing DC, reusing the entries for
+/// lower DCs that each explosion leads to. Once filled, every query is a lookup.
+#[derive(Debug, Clone)]
+pub struct ProbabilityCube<P = f64, D = Die> {
+    /// Every die in the cube, including every die that the requested dice can explode into.
+    dice: Vec<D>,
+
+    /// The position of each die in `dice`.
+    indices: HashMap<D, usize>,
+
+    /// The highest number of turbo tokens in the cube.
+    max_tokens: u32,
+
+    /// The highest DC in the cube.
+    max_dc: u32,
+
+    /// The probability of each entry, indexed by die, then turbo tokens, then DC (starting at 0).
+    entries: Vec<P>,
+}
+
+impl<P: Probability, D: ExplodingDie> ProbabilityCube<P, D> {
+    /// Fills a cube for the given dice, up to and including the given number of turbo tokens and
+    /// DC.
+    pub fn new(dice: &[D], max_tokens: u32, max_dc: u32) -> Self {
+        // Find every die that can be reached by exploding the requested dice.
+        let mut all_dice = Vec::new();
+        let mut indices = HashMap::new();
+        for &die in dice {
+            let mut die = Some(die);
+            while let Some(current) = die.filter(|die| !indices.contains_key(die)) {
+                indices.insert(current, all_dice.len());
+                all_dice.push(current);
+                die = current.explode();
+            }
+        }
+
+        let mut cube = ProbabilityCube {
+            dice: all_dice,
+            indices,
+            max_tokens,
+            max_dc,
+            entries: Vec::new(),
+        };
+        cube.fill();
+        cube
+    }
+
+    /// Returns the dice in the cube, including every die that the requested dice can explode into.
+    pub fn dice(&self) -> &[D] {
+        &self.dice
+    }
+
+    /// Returns the highest number of turbo tokens in the cube.
+    pub fn max_tokens(&self) -> u32 {
+        self.max_tokens
+    }
+
+    /// Returns the highest DC in the cube.
+    pub fn max_dc(&self) -> u32 {
+        self.max_dc
+    }
+
+    /// Returns the probability of beating the given DC when starting with the given die type and
+    /// number of turbo tokens, as described in
+    /// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens).
+    ///
+    /// Returns [`None`] if the die, number of turbo tokens, or DC is not in the cube.
+    pub fn probability(&self, die: D, turbo_tokens: u32, dc: u32) -> Option<&P> {
+        if turbo_tokens > self.max_tokens || dc > self.max_dc {
+            return None;
+        }
+        let &die = self.indices.get(&die)?;
+        self.entries.get(self.offset(die, turbo_tokens, dc))
+    }
+
+    /// Returns the probability of beating the given DC with a flat modifier applied to the roll,
+    /// as described in
+    /// [`probability_of_success_with_modifier`](crate::probability_of_success_with_modifier).
+    ///
+    /// Returns [`None`] if any entry the probability depends on is not in the cube. A negative
+    /// modifier that is only added to the total depends on the entry for a DC that much higher.
+    pub fn probability_with_modifier(
+        &self,
+        die: D,
+        turbo_tokens: u32,
+        dc: u32,
+        modifier: Modifier,
+    ) -> Option<P> {
+        with_modifier(die, turbo_tokens, dc, modifier, |die, turbo_tokens, dc| {
+            self.probability(die, turbo_tokens, dc).cloned()
+        })
+    }
+
+    /// Returns the probability of beating the given DC when rolling with advantage or
+    /// disadvantage, as described in
+    /// [`probability_of_success_with_advantage`](crate::probability_of_success_with_advantage).
+    ///
+    /// Returns [`None`] if any entry the probability depends on is not in the cube.
+    pub fn probability_with_advantage(
+        &self,
+        die: D,
+        turbo_tokens: u32,
+        dc: u32,
+        mode: RollMode,
+    ) -> Option<P> {
+        with_advantage(die, turbo_tokens, dc, mode, |die, turbo_tokens, dc| {
+            self.probability(die, turbo_tokens, dc).cloned()
+        })
+    }
+
+    /// Returns the position of an entry in `entries`.
+    fn offset(&self, die: usize, turbo_tokens: u32, dc: u32) -> usize {
+        let dcs = self.max_dc as usize + 1;
+        let tokens = self.max_tokens as usize + 1;
+        (die * tokens + turbo_tokens as usize) * dcs + dc as usize
+    }
+
+    /// Computes every entry in the cube.
+    fn fill(&mut self) {
+        let len = self.dice.len() * (self.max_tokens as usize + 1) * (self.max_dc as usize + 1);
+        self.entries = vec![P::zero(); len];
+
+        // Exploding always lowers the DC left to beat, so every entry only depends on entries with
+        // lower DCs, which have already been computed.
+        for dc in 0..=self.max_dc {
+            for die in 0..self.dice.len() {
+                for turbo_tokens in 0..=self.max_tokens {
+                    let p = self.entry(die, turbo_tokens, dc);
+                    let offset = self.offset(die, turbo_tokens, dc);
+                    self.entries[offset] = p;
+                }
+            }
+        }
+    }
+
+    /// Computes a single entry from the entries with lower DCs. This follows the same steps as
+    /// [`probability_of_success_with_turbo_tokens_in`](crate::probability_of_success_with_turbo_tokens_in),
+    /// and produces the same results.
+    fn entry(&self, die: usize, turbo_tokens: u32, dc: u32) -> P {
+        let sides = self.dice[die].sides();
+
+        // Can always roll a 1 or higher.
+        if dc <= 1 {
+            return P::one();
+        }
+
+        // If the DC is lower than or equal to the maximum value of the die.
+        if dc <= sides {
+            return P::ratio((sides - dc + 1 + turbo_tokens).min(sides), sides);
+        }
+
+        // If the die cannot explode, turbo tokens can still be added to the roll to beat the DC.
+        let Some(next) = self.dice[die].explode() else {
+            return P::ratio((sides + 1 + turbo_tokens).saturating_sub(dc).min(sides), sides);
+        };
+        let next = self.indices[&next];
+
+        // If the DC is higher than the maximum value of the die, look up the entry for each roll that
+        // can explode.
+        (1..=sides)
+            .map(|roll| {
+                if roll + turbo_tokens < sides {
+                    return P::zero();
+                }
+
+                let tokens_needed_to_explode = sides - roll;
+                let offset = self.offset(next, turbo_tokens - tokens_needed_to_explode, dc - sides);
+                self.entries[offset].clone() / P::ratio(sides, 1)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        probability_of_success_with_turbo_tokens_in,
+        testing::{sweep, Case, MAX_DC, MAX_TOKENS},
+    };
+
+    #[test]
+    fn matches_recursion() {
+        let cube = ProbabilityCube::<f64>::new(&Die::ALL, MAX_TOKENS, MAX_DC);
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            assert_eq!(
+                cube.probability(die, turbo_tokens, dc),
+                Some(&probability_of_success_with_turbo_tokens_in::<f64>(die, turbo_tokens, dc)),
+                "{}",
+                case,
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::{fmt::Display, hash::Hash};
+
+/// A die that explodes into another die when it rolls its maximum value.
+///
+/// This is implemented for [`Die`], which follows the ladder of dice used in Never Stop Blowing
+/// Up, and for [`Rung`](crate::Rung), which follows a user-defined [`Ladder`](crate::Ladder). All
+/// probability calculations accept any type implementing this trait.
+pub trait ExplodingDie: Copy + Eq + Hash + Display {
+    /// Returns the number of sides on the die.
+    fn sides(&self) -> u32;
+
+    /// Returns the die rolled after this die explodes, or [`None`] if this die cannot explode.
+    fn explode(&self) -> Option<Self>;
+}
+
+/// Kinds of dice available in Never Stop Blowing Up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Die {
+    D4,
+    D6,
+    D8,
+    D10,
+    D12,
+    D20,
+}
+
+impl std::fmt::Display for Die {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Die::D4 => "d4",
+            Die::D6 => "d6",
+            Die::D8 => "d8",
+            Die::D10 => "d10",
+            Die::D12 => "d12",
+            Die::D20 => "d20",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl Die {
+    /// All kinds of dice, in order from smallest to largest.
+    pub const ALL: [Die; 6] = [Die::D4, Die::D6, Die::D8, Die::D10, Die::D12, Die::D20];
+
+    /// Returns the number of sides on the die.
+    pub fn sides(self) -> u32 {
+        match self {
+            Die::D4 => 4,
+            Die::D6 => 6,
+            Die::D8 => 8,
+            Die::D10 => 10,
+            Die::D12 => 12,
+            Die::D20 => 20,
+        }
+    }
+
+    /// Returns the next highest die type, saturating at a d20.
+    pub fn next(self) -> Die {
+        match self {
+            Die::D4 => Die::D6,
+            Die::D6 => Die::D8,
+            Die::D8 => Die::D10,
+            Die::D10 => Die::D12,
+            Die::D12 => Die::D20,
+            Die::D20 => Die::D20,
+        }
+    }
+}
+
+impl ExplodingDie for Die {
+    fn sides(&self) -> u32 {
+        Die::sides(*self)
+    }
+
+    fn explode(&self) -> Option<Self> {
+        Some(self.next())
+    }
+}
+
+/// An error returned when parsing a [`Die`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDieError(String);
+
+impl std::fmt::Display for ParseDieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "unknown die `{}`, expected one of d4, d6, d8, d10, d12, d20", self.0)
+    }
+}
+
+impl std::error::Error for ParseDieError {}
+
+impl std::str::FromStr for Die {
+    type Err = ParseDieError;
+
+    /// Parses a die from its name, such as `d8`. The leading `d` is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sides = s.trim().trim_start_matches(['d', 'D']);
+        Die::ALL
+            .into_iter()
+            .find(|die| die.sides().to_string() == sides)
+            .ok_or_else(|| ParseDieError(s.to_string()))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{ExplodingDie, Probability, ProbabilityCube};
+
+/// The probability mass function of the final total of a roll, truncated at a cutoff.
+///
+/// Since a d20 can keep exploding forever, the total of a roll has no upper bound. The
+/// distribution therefore only stores the probability of each total up to and including the
+/// cutoff, and reports the remaining probability mass of all higher totals as the tail.
+#[derive(Debug, Clone)]
+pub struct Distribution<P = f64> {
+    /// The probability of each total, where index `i` holds the probability of a total of `i + 1`.
+    masses: Vec<P>,
+
+    /// The probability of a total higher than the cutoff.
+    tail: P,
+}
+
+impl<P: Probability> Distribution<P> {
+    /// Returns the highest total whose probability is stored in the distribution.
+    pub fn cutoff(&self) -> u32 {
+        self.masses.len() as u32
+    }
+
+    /// Returns the probability of rolling exactly the given total.
+    ///
+    /// Returns [`None`] if the total is higher than the cutoff.
+    pub fn probability(&self, total: u32) -> Option<P> {
+        match total {
+            0 => Some(P::zero()),
+            _ => self.masses.get(total as usize - 1).cloned(),
+        }
+    }
+
+    /// Returns the probability of rolling a total higher than the cutoff.
+    pub fn tail(&self) -> &P {
+        &self.tail
+    }
+
+    /// Returns an iterator over each total from 1 up to the cutoff, along with its probability.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &P)> {
+        (1..).zip(self.masses.iter())
+    }
+}
+
+/// Computes the distribution of the final total when starting with the given die type and number
+/// of turbo tokens, up to and including the given cutoff.
+///
+/// Turbo tokens are spent to explode the die whenever the player has enough of them to do so, and
+/// any tokens left over are added to the final roll. Under this policy, the probability of rolling
+/// a total of at least some DC is exactly the probability given by
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens).
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `cutoff` - The highest total to compute the probability of.
+pub fn distribution(die: impl ExplodingDie, turbo_tokens: u32, cutoff: u32) -> Distribution {
+    distribution_in(die, turbo_tokens, cutoff)
+}
+
+/// Same as [`distribution`], but carries out the calculation in the given number type.
+pub fn distribution_in<P: Probability>(
+    die: impl ExplodingDie,
+    turbo_tokens: u32,
+    cutoff: u32,
+) -> Distribution<P> {
+    // The probability of rolling a total of at least `total` is the probability of beating a DC of
+    // `total`, so the probability of rolling exactly `total` is the difference of adjacent DCs.
+    let cube = ProbabilityCube::<P, _>::new(&[die], turbo_tokens, cutoff + 1);
+    let at_least = (1..=cutoff + 1)
+        .map(|total| cube.probability(die, turbo_tokens, total).cloned().unwrap_or_else(P::zero))
+        .collect::<Vec<_>>();
+
+    let masses = at_least
+        .windows(2)
+        .map(|pair| pair[0].clone() - pair[1].clone())
+        .collect();
+    let tail = at_least[at_least.len() - 1].clone();
+
+    Distribution { masses, tail }
+}
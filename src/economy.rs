@@ -0,0 +1,259 @@
+use crate::{Die, ExplodingDie, Probability};
+use std::collections::HashMap;
+
+/// The largest total change in probability between two iterations at which [`TokenEconomy`]
+/// considers the distribution of turbo tokens to have settled.
+pub const STATIONARY_TOLERANCE: f64 = 1e-12;
+
+/// The most iterations [`TokenEconomy`] makes before giving up on the distribution settling.
+const MAX_ITERATIONS: usize = 1_000_000;
+
+/// The long-run behavior of the turbo tokens held by a player making the same schedule of checks
+/// over and over.
+///
+/// Failing a check grants a turbo token, and holding turbo tokens makes later checks more likely
+/// to succeed, so the number of tokens held before each check forms a Markov chain. Tokens are
+/// spent as described in
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens),
+/// so the probability of each check succeeding with a given number of tokens is exactly the
+/// probability that function returns. The chain additionally tracks how many tokens each check
+/// spends, which depends on the dice rolled.
+///
+/// The chain is run from a starting number of tokens until the distribution of tokens held settles.
+/// Tokens earned beyond `max_tokens` are not kept, which keeps the chain finite.
+#[derive(Debug, Clone)]
+pub struct TokenEconomy<D = Die> {
+    /// The type of die rolled for each check.
+    pub die: D,
+
+    /// The difficulty class of each check in the schedule, in order.
+    pub dcs: Vec<u32>,
+
+    /// The most turbo tokens the player can hold.
+    pub max_tokens: u32,
+
+    /// The long-run probability of holding each number of turbo tokens, from 0 to `max_tokens`,
+    /// before each check in the schedule.
+    pub distributions: Vec<Vec<f64>>,
+
+    /// The long-run probability of succeeding at each check in the schedule.
+    pub success_probabilities: Vec<f64>,
+}
+
+impl<D: ExplodingDie> TokenEconomy<D> {
+    /// Computes the long-run distribution of turbo tokens held when repeatedly making the given
+    /// schedule of checks.
+    ///
+    /// # Arguments
+    ///
+    /// * `die` - The type of die rolled for each check.
+    /// * `dcs` - The difficulty class of each check in the schedule, in order.
+    /// * `max_tokens` - The most turbo tokens the player can hold.
+    /// * `turbo_tokens` - The number of turbo tokens held before the first check.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dcs` is empty, or if `turbo_tokens` is greater than `max_tokens`.
+    pub fn new(die: D, dcs: &[u32], max_tokens: u32, turbo_tokens: u32) -> Self {
+        assert!(!dcs.is_empty(), "at least one check is required");
+        assert!(turbo_tokens <= max_tokens, "cannot start with more than the maximum tokens");
+
+        let mut outcomes = Outcomes::default();
+        let kernels = dcs
+            .iter()
+            .map(|&dc| {
+                (0..=max_tokens)
+                    .map(|tokens| outcomes.get(die, tokens, dc))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Run the chain one schedule at a time. Averaging each step with the previous distribution
+        // keeps the same stationary distribution, but prevents the chain from cycling between
+        // distributions forever if it is periodic.
+        let mut distribution = vec![0.0; max_tokens as usize + 1];
+        distribution[turbo_tokens as usize] = 1.0;
+        for _ in 0..MAX_ITERATIONS {
+            let mut next = distribution.clone();
+            for kernel in &kernels {
+                next = step(&next, kernel, max_tokens).0;
+            }
+
+            let change = distribution
+                .iter()
+                .zip(&next)
+                .map(|(p, q)| (p - q).abs())
+                .sum::<f64>()
+                / 2.0;
+            distribution = distribution.iter().zip(&next).map(|(p, q)| (p + q) / 2.0).collect();
+            if change <= STATIONARY_TOLERANCE {
+                break;
+            }
+        }
+
+        let mut distributions = Vec::with_capacity(dcs.len());
+        let mut success_probabilities = Vec::with_capacity(dcs.len());
+        for kernel in &kernels {
+            let (next, success) = step(&distribution, kernel, max_tokens);
+            distributions.push(distribution);
+            success_probabilities.push(success);
+            distribution = next;
+        }
+
+        TokenEconomy {
+            die,
+            dcs: dcs.to_vec(),
+            max_tokens,
+            distributions,
+            success_probabilities,
+        }
+    }
+
+    /// Returns the long-run fraction of checks that succeed.
+    pub fn success_rate(&self) -> f64 {
+        self.success_probabilities.iter().sum::<f64>() / self.success_probabilities.len() as f64
+    }
+
+    /// Returns the long-run expected number of turbo tokens held before each check in the
+    /// schedule.
+    pub fn expected_tokens(&self) -> Vec<f64> {
+        self.distributions
+            .iter()
+            .map(|distribution| {
+                distribution.iter().enumerate().map(|(tokens, p)| tokens as f64 * p).sum()
+            })
+            .collect()
+    }
+}
+
+/// The probability of each way a single check can end, for a fixed number of turbo tokens held
+/// before the check.
+#[derive(Debug, Clone)]
+pub(crate) struct Outcome<P = f64> {
+    /// The probability of succeeding with each number of turbo tokens left over.
+    pub(crate) successes: Vec<P>,
+
+    /// The probability of failing with each number of turbo tokens left over, before the token
+    /// granted for failing.
+    pub(crate) failures: Vec<P>,
+}
+
+/// Memoized [`Outcome`]s, keyed by the die being rolled, the number of turbo tokens held, and the
+/// remaining DC.
+#[derive(Debug)]
+pub(crate) struct Outcomes<P, D> {
+    memo: HashMap<(D, u32, u32), Outcome<P>>,
+}
+
+impl<P, D> Default for Outcomes<P, D> {
+    fn default() -> Self {
+        Outcomes { memo: HashMap::new() }
+    }
+}
+
+impl<P: Probability, D: ExplodingDie> Outcomes<P, D> {
+    /// Returns the outcome of rolling `die` with `dc` left to beat, spending turbo tokens only if
+    /// necessary, in the same way as [`roll`](crate::roll).
+    pub(crate) fn get(&mut self, die: D, turbo_tokens: u32, dc: u32) -> Outcome<P> {
+        let key = (die, turbo_tokens, dc);
+        if let Some(outcome) = self.memo.get(&key) {
+            return outcome.clone();
+        }
+
+        let len = turbo_tokens as usize + 1;
+        let mut outcome = Outcome {
+            successes: vec![P::zero(); len],
+            failures: vec![P::zero(); len],
+        };
+
+        if dc <= 1 {
+            outcome.successes[turbo_tokens as usize] = P::one();
+            self.memo.insert(key, outcome.clone());
+            return outcome;
+        }
+
+        let sides = die.sides();
+        let chance = P::ratio(1, sides);
+        let next = die.explode();
+        let add = |p: &mut P, q: P| *p = p.clone() + q;
+        for roll in 1..=sides {
+            // Where the roll ends up, as the die to explode into (if any), the tokens left, and
+            // the remaining DC. A roll that does not explode either succeeds or fails outright.
+            let explosion = match next {
+                Some(next) if roll == sides && dc > sides => Some((next, turbo_tokens)),
+                Some(next) if dc > sides && roll + turbo_tokens >= sides => {
+                    Some((next, turbo_tokens - (sides - roll)))
+                },
+                _ => None,
+            };
+
+            match explosion {
+                Some((next, tokens)) => {
+                    let rest = self.get(next, tokens, dc - sides);
+                    for (left, p) in rest.successes.into_iter().enumerate() {
+                        add(&mut outcome.successes[left], chance.clone() * p);
+                    }
+                    for (left, p) in rest.failures.into_iter().enumerate() {
+                        add(&mut outcome.failures[left], chance.clone() * p);
+                    }
+                },
+                None if roll >= dc || next.is_some() && roll == sides => {
+                    add(&mut outcome.successes[turbo_tokens as usize], chance.clone());
+                },
+                None if next.is_some() && dc > sides => {
+                    add(&mut outcome.failures[turbo_tokens as usize], chance.clone());
+                },
+                None if roll + turbo_tokens >= dc => {
+                    add(&mut outcome.successes[(turbo_tokens - (dc - roll)) as usize], chance.clone());
+                },
+                None => add(&mut outcome.failures[turbo_tokens as usize], chance.clone()),
+            }
+        }
+
+        self.memo.insert(key, outcome.clone());
+        outcome
+    }
+}
+
+/// Advances a distribution of turbo tokens held through a single check, returning the
+/// distribution after the check along with the probability that the check succeeds.
+fn step(distribution: &[f64], kernel: &[Outcome<f64>], max_tokens: u32) -> (Vec<f64>, f64) {
+    let mut next = vec![0.0; distribution.len()];
+    let mut success = 0.0;
+    for (p, outcome) in distribution.iter().zip(kernel) {
+        for (left, q) in outcome.successes.iter().enumerate() {
+            next[left] += p * q;
+            success += p * q;
+        }
+        for (left, q) in outcome.failures.iter().enumerate() {
+            next[(left + 1).min(max_tokens as usize)] += p * q;
+        }
+    }
+    (next, success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{probability_of_success_with_turbo_tokens_in, testing::{sweep, Case}, Exact};
+
+    #[test]
+    fn outcomes_match_probability_of_success() {
+        let mut outcomes = Outcomes::<Exact, Die>::default();
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            let outcome = outcomes.get(die, turbo_tokens, dc);
+            assert_eq!(
+                outcome.successes.iter().cloned().sum::<Exact>(),
+                probability_of_success_with_turbo_tokens_in::<Exact>(die, turbo_tokens, dc),
+                "{}",
+                case,
+            );
+            assert_eq!(
+                outcome.successes.into_iter().chain(outcome.failures).sum::<Exact>(),
+                Exact::one(),
+                "{}",
+                case,
+            );
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::probability::Probability;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+
+/// An exact probability, represented as a fraction of two arbitrarily large integers.
+///
+/// Displays as `numerator/denominator` in lowest terms (e.g. `1/92160`), or as a plain integer if
+/// the denominator is 1.
+pub type Exact = BigRational;
+
+impl Probability for Exact {
+    fn zero() -> Self {
+        Zero::zero()
+    }
+
+    fn one() -> Self {
+        One::one()
+    }
+
+    fn ratio(numerator: u32, denominator: u32) -> Self {
+        BigRational::new(BigInt::from(numerator), BigInt::from(denominator))
+    }
+
+    fn to_f64(&self) -> f64 {
+        ToPrimitive::to_f64(self).unwrap_or(f64::NAN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        probability_of_success_in,
+        probability_of_success_with_turbo_tokens_in,
+        testing::{sweep, Case},
+        Die,
+    };
+
+    fn exact(numerator: u32, denominator: u32) -> Exact {
+        Exact::ratio(numerator, denominator)
+    }
+
+    #[test]
+    fn known_values() {
+        assert_eq!(probability_of_success_in::<Exact>(Die::D4, 3), exact(1, 2));
+        assert_eq!(probability_of_success_in::<Exact>(Die::D4, 56), exact(1, 92160));
+        assert_eq!(probability_of_success_in::<Exact>(Die::D4, 57), exact(1, 115200));
+        let p = probability_of_success_with_turbo_tokens_in::<Exact>(Die::D6, 2, 10);
+        assert_eq!(p, exact(3, 8));
+    }
+
+    #[test]
+    fn matches_f64() {
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            let exact = probability_of_success_with_turbo_tokens_in::<Exact>(die, turbo_tokens, dc);
+            let float = probability_of_success_with_turbo_tokens_in::<f64>(die, turbo_tokens, dc);
+            assert!(
+                (Probability::to_f64(&exact) - float).abs() <= 1e-12,
+                "{}: {} != {}",
+                case,
+                exact,
+                float,
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{probability_of_success_with_turbo_tokens_in, Die, ExplodingDie, Probability};
+
+/// A member of a party making a group check: the die they roll for the skill being checked, and
+/// the turbo tokens they hold.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Member<D = Die> {
+    /// The type of die the member rolls.
+    pub die: D,
+
+    /// The number of turbo tokens available to the member.
+    pub turbo_tokens: u32,
+}
+
+impl<D: ExplodingDie> std::fmt::Display for Member<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.die, self.turbo_tokens)
+    }
+}
+
+impl<D> std::str::FromStr for Member<D>
+where
+    D: std::str::FromStr,
+    D::Err: std::fmt::Display,
+{
+    type Err = String;
+
+    /// Parses a member of the form `die:tokens`, such as `d8:2`, or just `die` for a member with
+    /// no turbo tokens.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (die, turbo_tokens) = match s.split_once(':') {
+            Some((die, tokens)) => {
+                let tokens = tokens
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("invalid turbo tokens `{}`: {}", tokens, e))?;
+                (die, tokens)
+            },
+            None => (s, 0),
+        };
+        Ok(Member {
+            die: die.parse().map_err(|e| format!("{}", e))?,
+            turbo_tokens,
+        })
+    }
+}
+
+/// The probability of each number of party members succeeding at a group check, where every
+/// member rolls against the same DC.
+///
+/// Each member spends their own turbo tokens as described in
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens),
+/// and the members' rolls are independent.
+#[derive(Debug, Clone)]
+pub struct GroupCheck<P = f64> {
+    /// The probability of each number of members succeeding, where index `k` holds the
+    /// probability of exactly `k` successes.
+    successes: Vec<P>,
+}
+
+impl<P: Probability> GroupCheck<P> {
+    /// Computes the group check for the given party members and DC.
+    pub fn new<D: ExplodingDie>(members: &[Member<D>], dc: u32) -> Self {
+        // Add one member at a time, tracking the distribution of the number of successes so far.
+        let mut successes = vec![P::one()];
+        for member in members {
+            let p =
+                probability_of_success_with_turbo_tokens_in::<P>(member.die, member.turbo_tokens, dc);
+            let q = P::one() - p.clone();
+
+            let mut next = vec![P::zero(); successes.len() + 1];
+            for (k, r) in successes.into_iter().enumerate() {
+                next[k] = next[k].clone() + r.clone() * q.clone();
+                next[k + 1] = next[k + 1].clone() + r * p.clone();
+            }
+            successes = next;
+        }
+
+        GroupCheck { successes }
+    }
+
+    /// Returns the number of members making the check.
+    pub fn members(&self) -> usize {
+        self.successes.len() - 1
+    }
+
+    /// Returns the probability that exactly `k` members succeed.
+    pub fn exactly(&self, k: usize) -> P {
+        self.successes.get(k).cloned().unwrap_or_else(P::zero)
+    }
+
+    /// Returns the probability that at least `k` members succeed, which is zero if `k` is more than
+    /// the number of members.
+    pub fn at_least(&self, k: usize) -> P {
+        if k > self.members() {
+            return P::zero();
+        }
+        self.successes.iter().skip(k).cloned().sum()
+    }
+
+    /// Returns the probability that every member succeeds.
+    pub fn all(&self) -> P {
+        self.exactly(self.members())
+    }
+
+    /// Returns the probability that the best roll in the party beats the DC, which is the
+    /// probability that at least one member succeeds.
+    pub fn best(&self) -> P {
+        self.at_least(1)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::ExplodingDie;
+
+/// What happens when the die at the top of a [`Ladder`] rolls its maximum value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Saturation {
+    /// The top die explodes into itself, and is rerolled until it no longer rolls its maximum
+    /// value. This is how a d20 behaves in Never Stop Blowing Up.
+    Reroll,
+
+    /// The top die cannot explode.
+    Cap,
+}
+
+impl std::fmt::Display for Saturation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Saturation::Reroll => "reroll",
+            Saturation::Cap => "cap",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An error returned when a [`Ladder`] is invalid or cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LadderError(String);
+
+impl std::fmt::Display for LadderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for LadderError {}
+
+/// A user-defined sequence of dice that a die explodes through, such as the d4 → d6 → d8 → d10 →
+/// d12 → d20 ladder of [`Die`](crate::Die).
+///
+/// Each die on the ladder is a [`Rung`], which can be used anywhere an [`ExplodingDie`] is
+/// accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ladder {
+    /// The number of sides on each die, from the bottom of the ladder to the top.
+    sides: Vec<u32>,
+
+    /// What happens when the top die rolls its maximum value.
+    saturation: Saturation,
+}
+
+impl Ladder {
+    /// Creates a ladder of dice with the given numbers of sides, from the bottom of the ladder to
+    /// the top.
+    ///
+    /// Returns an error if the ladder is empty or if any die has fewer than two sides.
+    pub fn new(sides: Vec<u32>, saturation: Saturation) -> Result<Self, LadderError> {
+        if sides.is_empty() {
+            return Err(LadderError("a ladder must contain at least one die".to_string()));
+        }
+        if let Some(&bad) = sides.iter().find(|&&sides| sides < 2) {
+            return Err(LadderError(format!("a die must have at least two sides, found d{}", bad)));
+        }
+        Ok(Ladder { sides, saturation })
+    }
+
+    /// Returns the ladder used in Never Stop Blowing Up: d4 → d6 → d8 → d10 → d12 → d20, where the
+    /// d20 is rerolled when it rolls its maximum value.
+    pub fn standard() -> Self {
+        Ladder {
+            sides: vec![4, 6, 8, 10, 12, 20],
+            saturation: Saturation::Reroll,
+        }
+    }
+
+    /// Returns what happens when the top die rolls its maximum value.
+    pub fn saturation(&self) -> Saturation {
+        self.saturation
+    }
+
+    /// Returns every die on the ladder, from the bottom to the top.
+    pub fn rungs(&self) -> impl Iterator<Item = Rung<'_>> {
+        (0..self.sides.len()).map(move |index| Rung { ladder: self, index })
+    }
+
+    /// Returns the lowest die on the ladder with the given number of sides, if any.
+    pub fn rung_with_sides(&self, sides: u32) -> Option<Rung<'_>> {
+        self.rungs().find(|rung| rung.sides() == sides)
+    }
+}
+
+impl std::fmt::Display for Ladder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let sides = self.sides.iter().map(|sides| sides.to_string()).collect::<Vec<_>>();
+        write!(f, "{}:{}", sides.join(","), self.saturation)
+    }
+}
+
+impl std::str::FromStr for Ladder {
+    type Err = LadderError;
+
+    /// Parses a ladder from a comma-separated list of side counts, optionally followed by a colon
+    /// and the saturation behavior, such as `4,6,8,12,100:reroll` or `4,6,8,12:cap`. Each side
+    /// count can be prefixed with `d`. The saturation behavior defaults to rerolling.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sides, saturation) = match s.split_once(':') {
+            Some((sides, saturation)) => (sides, Some(saturation.trim())),
+            None => (s, None),
+        };
+
+        let saturation = match saturation {
+            None | Some("reroll") => Saturation::Reroll,
+            Some("cap") => Saturation::Cap,
+            Some(other) => {
+                return Err(LadderError(format!(
+                    "unknown saturation behavior `{}`, expected `reroll` or `cap`",
+                    other,
+                )));
+            },
+        };
+
+        let sides = sides
+            .split(',')
+            .map(|die| {
+                die.trim()
+                    .trim_start_matches(['d', 'D'])
+                    .parse::<u32>()
+                    .map_err(|_| LadderError(format!("invalid die `{}`", die.trim())))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ladder::new(sides, saturation)
+    }
+}
+
+impl serde::Serialize for Ladder {
+    /// Serializes the ladder as a string, in the same format that it is parsed from.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Ladder {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A single die on a [`Ladder`].
+///
+/// Two rungs are equal only if they are at the same position on the same ladder instance.
+#[derive(Copy, Clone)]
+pub struct Rung<'a> {
+    /// The ladder the die belongs to.
+    ladder: &'a Ladder,
+
+    /// The position of the die on the ladder, where 0 is the bottom.
+    index: usize,
+}
+
+impl Rung<'_> {
+    /// Returns the position of the die on its ladder, where 0 is the bottom.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl PartialEq for Rung<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.ladder, other.ladder) && self.index == other.index
+    }
+}
+
+impl Eq for Rung<'_> {}
+
+impl std::hash::Hash for Rung<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self.ladder, state);
+        self.index.hash(state);
+    }
+}
+
+impl std::fmt::Debug for Rung<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Rung({}, {})", self.index, self)
+    }
+}
+
+impl std::fmt::Display for Rung<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "d{}", self.sides())
+    }
+}
+
+impl ExplodingDie for Rung<'_> {
+    fn sides(&self) -> u32 {
+        self.ladder.sides[self.index]
+    }
+
+    fn explode(&self) -> Option<Self> {
+        if self.index + 1 < self.ladder.sides.len() {
+            return Some(Rung { ladder: self.ladder, index: self.index + 1 });
+        }
+
+        match self.ladder.saturation {
+            Saturation::Reroll => Some(*self),
+            Saturation::Cap => None,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Computes the probability of beating various DCs in Dimension 20's Never Stop Blowing Up.
+//!
+//! The crate exposes the [`Die`] type along with functions computing the probability of beating a
+//! given difficulty class, with or without the use of turbo tokens. Each function has an `_in`
+//! variant that is generic over the [`Probability`] type the calculation is carried out in, which
+//! can be used with [`Exact`] to compute probabilities as exact fractions. Conversely,
+//! [`highest_dc_with_probability`] finds the DC that gives a desired probability of success.
+//! Static bonuses and penalties are applied with a [`Modifier`], whose [`ModifierRule`] decides
+//! whether it helps dice explode, and checks can be rolled with advantage or disadvantage with
+//! [`probability_of_success_with_advantage`].
+//!
+//! Homebrew rules can replace the standard dice with a custom [`Ladder`], whose dice can be used
+//! wherever an [`ExplodingDie`] is accepted. For large ranges of DCs and turbo tokens, a
+//! [`ProbabilityCube`] computes every probability once up front. A [`GroupCheck`] combines the
+//! probabilities of several party members rolling against the same DC.
+//!
+//! The full [`Distribution`] of roll totals can also be computed with [`distribution`],
+//! summarized with [`statistics`], and pitted against another die's in a [`contest`]. A [`Table`]
+//! collects the probabilities of beating a range of DCs with several dice, and can be rendered in
+//! various formats with the functions in [`output`].
+//!
+//! When turbo tokens are better saved for later checks, the [`Solver`] finds the optimal way to
+//! spend them over a sequence of checks, and [`allocate`] shares a pool of them between several
+//! players making checks in turn. A [`TokenEconomy`] finds how many turbo tokens a player holds in
+//! the long run when making the same checks over and over.
+//!
+//! The analytic results can be cross-checked by physically rolling dice with [`roll`] and
+//! [`simulate`]. At the table, a [`Session`] tracks each player's skill dice and turbo tokens
+//! between checks, while a [`Progression`] models how their skill dice advance over a campaign.
+
+mod advantage;
+mod contest;
+mod cube;
+mod die;
+mod distribution;
+mod economy;
+mod exact;
+mod group;
+mod ladder;
+mod modifier;
+pub mod output;
+mod policy;
+mod pool;
+mod probability;
+mod progression;
+mod session;
+mod simulation;
+mod statistics;
+mod table;
+#[cfg(test)]
+mod testing;
+
+pub use advantage::{
+    probability_of_success_with_advantage,
+    probability_of_success_with_advantage_in,
+    RollMode,
+};
+pub use contest::{contest, Contest};
+pub use cube::ProbabilityCube;
+pub use die::{Die, ExplodingDie, ParseDieError};
+pub use distribution::{distribution, distribution_in, Distribution};
+pub use economy::{TokenEconomy, STATIONARY_TOLERANCE};
+pub use exact::Exact;
+pub use group::{GroupCheck, Member};
+pub use ladder::{Ladder, LadderError, Rung, Saturation};
+pub use modifier::{
+    probability_of_success_with_modifier,
+    probability_of_success_with_modifier_in,
+    Modifier,
+    ModifierRule,
+};
+pub use policy::{Action, Check, Solver};
+pub use pool::{allocate, allocate_in, Allocation};
+pub use probability::{
+    highest_dc_with_probability,
+    probability_of_success,
+    probability_of_success_in,
+    probability_of_success_with_turbo_tokens,
+    probability_of_success_with_turbo_tokens_in,
+    Probability,
+};
+pub use progression::{Advancement, Progression};
+pub use session::{CheckResult, Player, Session, SessionError};
+pub use simulation::{roll, roll_with, simulate, Estimate, Offer, Purpose, Roll, Step, Z_95};
+pub use statistics::{statistics, Statistics, TAIL_TOLERANCE};
+pub use table::{format_fraction, format_percent, Row, Table};
@@ -1,164 +1,1337 @@
-//! Computes the probability of beating various DCs in Dimension 20's Never Stop Blowing Up.
+//! Command-line interface for computing the probability of beating various DCs in Dimension 20's
+//! Never Stop Blowing Up.
+//!
+//! Running without a subcommand prints the default probability tables.
 
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use exploding::{
+    allocate_in,
+    format_fraction,
+    format_percent,
+    highest_dc_with_probability,
+    output::{self, LatexEnvironment},
+    probability_of_success_with_advantage_in,
+    probability_of_success_with_modifier_in,
+    probability_of_success_with_turbo_tokens_in,
+    statistics,
+    Action,
+    Advancement,
+    Check,
+    Contest,
+    Exact,
+    ExplodingDie,
+    GroupCheck,
+    Ladder,
+    Member,
+    Modifier,
+    ModifierRule,
+    Offer,
+    Probability,
+    ProbabilityCube,
+    Progression,
+    Purpose,
+    RollMode,
+    Rung,
+    Session,
+    SessionError,
+    Solver,
+    Table,
+    TokenEconomy,
+    Z_95,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::{json, Value};
+use std::{io::Write, ops::RangeInclusive, path::PathBuf, str::FromStr};
 use tabled::{builder::Builder, settings::style::Style};
 
-/// Kinds of dice available in Never Stop Blowing Up.
-#[derive(Debug, Copy, Clone)]
-enum Die {
-    D4,
-    D6,
-    D8,
-    D10,
-    D12,
-    D20,
-}
-
-impl std::fmt::Display for Die {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let s = match self {
-            Die::D4 => "d4",
-            Die::D6 => "d6",
-            Die::D8 => "d8",
-            Die::D10 => "d10",
-            Die::D12 => "d12",
-            Die::D20 => "d20",
+#[derive(Debug, Parser)]
+#[command(version, about = "Probabilities of beating DCs in Never Stop Blowing Up")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The ladder of dice that a die explodes through, written as comma-separated side counts
+    /// optionally followed by `:reroll` or `:cap` to choose what the top die does when it rolls its
+    /// maximum value, e.g. `4,6,8,12,100:reroll`.
+    #[arg(long, global = true, default_value = "4,6,8,10,12,20:reroll")]
+    ladder: Ladder,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print tables of the probability of beating each DC, one table per turbo token count.
+    Table(TableArgs),
+
+    /// Print the probability of beating a single DC.
+    Query(QueryArgs),
+
+    /// Print statistics of the total rolled with a die.
+    Stats(StatsArgs),
+
+    /// Print the highest DC that each die beats with at least a given probability.
+    Dc(DcArgs),
+
+    /// Find the optimal way to spend turbo tokens on a check, accounting for future checks.
+    Policy(PolicyArgs),
+
+    /// Estimate probabilities by physically rolling dice many times.
+    Simulate(SimulateArgs),
+
+    /// Roll a die, printing every explosion along the way.
+    Roll(RollArgs),
+
+    /// Track players' skill dice and turbo tokens across checks.
+    Session(SessionArgs),
+
+    /// Print how a skill's die advances over a campaign of checks.
+    Progression(ProgressionArgs),
+
+    /// Print the long-run distribution of turbo tokens held when repeating a schedule of checks.
+    Economy(EconomyArgs),
+
+    /// Print the probability of each side winning a contest between two dice.
+    Contest(ContestArgs),
+
+    /// Print the probability of a party succeeding at a group check.
+    Group(GroupArgs),
+
+    /// Print the best way to share a pool of turbo tokens between players making checks in turn.
+    Pool(PoolArgs),
+
+    /// Draw charts of the probability of beating each DC as SVG files, one per turbo token count.
+    Chart(ChartArgs),
+
+    /// Print a color-graded heatmap of the probability of beating each DC with each die and turbo
+    /// token count.
+    Heatmap(HeatmapArgs),
+
+    /// Write every table to a single offline HTML page with sortable columns, a turbo token
+    /// selector, and a DC search box.
+    Report(ReportArgs),
+
+    /// Rewrite the tables in the README with the default tables.
+    Readme(ReadmeArgs),
+}
+
+#[derive(Debug, Args)]
+struct TableArgs {
+    #[command(flatten)]
+    tables: TablesArgs,
+
+    /// Print probabilities as exact fractions instead of floating-point numbers.
+    #[arg(long)]
+    exact: bool,
+
+    /// The format to print the tables in.
+    #[arg(long, value_enum, default_value_t = Format::Markdown)]
+    format: Format,
+
+    /// The number of decimal places to round percentages to in markdown and LaTeX tables.
+    #[arg(long, default_value_t = 6)]
+    precision: usize,
+
+    /// The LaTeX environment to typeset each table in.
+    #[arg(long, value_enum, default_value_t = LatexTable::Longtable)]
+    latex_environment: LatexTable,
+}
+
+impl Default for TableArgs {
+    fn default() -> Self {
+        TableArgs {
+            tables: TablesArgs::default(),
+            exact: false,
+            format: Format::Markdown,
+            precision: 6,
+            latex_environment: LatexTable::Longtable,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct ChartArgs {
+    #[command(flatten)]
+    tables: TablesArgs,
+
+    /// The directory to write the charts to. It is created if it does not exist.
+    #[arg(long, default_value = "charts")]
+    output_dir: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct HeatmapArgs {
+    #[command(flatten)]
+    tables: TablesArgs,
+
+    /// The format to print the heatmap in.
+    #[arg(long, value_enum, default_value_t = HeatmapFormat::Html)]
+    format: HeatmapFormat,
+
+    /// The file to write the heatmap to. Defaults to printing it.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+/// Output formats for heatmaps.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum HeatmapFormat {
+    /// A standalone SVG image.
+    Svg,
+
+    /// A standalone HTML page with each probability printed in its cell.
+    Html,
+}
+
+#[derive(Debug, Args)]
+struct ReportArgs {
+    #[command(flatten)]
+    tables: TablesArgs,
+
+    /// The file to write the report to.
+    #[arg(long, default_value = "report.html")]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct ReadmeArgs {
+    /// The README to rewrite.
+    #[arg(long, default_value = "README.md")]
+    path: PathBuf,
+
+    /// Fail instead of rewriting the README if its tables are out of date.
+    #[arg(long)]
+    check: bool,
+}
+
+/// The markers around the tables in the README, which are replaced by the `readme` subcommand.
+const README_START: &str = "<!-- tables:start -->";
+const README_END: &str = "<!-- tables:end -->";
+
+/// The contents of a set of tables, one per turbo token count.
+#[derive(Debug, Args)]
+struct TablesArgs {
+    /// Comma-separated list of dice to include, e.g. `d4,d8`. Defaults to every die on the ladder.
+    #[arg(long, value_delimiter = ',')]
+    dice: Vec<DieName>,
+
+    /// The highest DC to include.
+    #[arg(long, default_value_t = 80)]
+    max_dc: u32,
+
+    /// The turbo token counts to include, one table per count, e.g. `2`, `0..3`, or `0..=3`.
+    #[arg(long, value_parser = parse_range, default_value = "0..=5")]
+    tokens: RangeInclusive<u32>,
+
+    #[command(flatten)]
+    modifier: ModifierArgs,
+
+    #[command(flatten)]
+    advantage: AdvantageArgs,
+}
+
+impl Default for TablesArgs {
+    fn default() -> Self {
+        TablesArgs {
+            dice: Vec::new(),
+            max_dc: 80,
+            tokens: 0..=5,
+            modifier: ModifierArgs::default(),
+            advantage: AdvantageArgs::default(),
+        }
+    }
+}
+
+impl TablesArgs {
+    /// Computes the tables, filling a single cube for all of them.
+    fn build<'a, P: Probability>(&self, ladder: &'a Ladder) -> Vec<Table<P, Rung<'a>>> {
+        let dice = resolve_dice(ladder, &self.dice);
+        let modifier = self.modifier.modifier();
+        let mode = self.advantage.mode();
+
+        // A penalty only added to the total needs the probabilities of beating higher DCs.
+        let max_dc = self.max_dc.saturating_add(modifier.value.min(0).unsigned_abs());
+        let cube = ProbabilityCube::new(&dice, *self.tokens.end(), max_dc);
+        self.tokens
+            .clone()
+            .map(|turbo_tokens| {
+                let dcs = 1..=self.max_dc;
+                match mode {
+                    RollMode::Normal => {
+                        Table::from_cube_with_modifier(&cube, &dice, turbo_tokens, dcs, modifier)
+                    },
+                    mode => Table::from_cube_with_advantage(&cube, &dice, turbo_tokens, dcs, mode),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Output formats for tables.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Markdown tables of rounded percentages, or fractions with `--exact`.
+    Markdown,
+
+    /// A single CSV document of raw probabilities.
+    Csv,
+
+    /// A JSON array of tables of raw probabilities.
+    Json,
+
+    /// LaTeX tables of rounded percentages, or fractions with `--exact`, using the `booktabs`
+    /// package.
+    Latex,
+}
+
+/// LaTeX environments for tables, as chosen on the command line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum LatexTable {
+    /// A `tabular`, which cannot break across pages.
+    Tabular,
+
+    /// A `longtable`, which breaks across pages. Requires the `longtable` package.
+    Longtable,
+}
+
+impl From<LatexTable> for LatexEnvironment {
+    fn from(environment: LatexTable) -> Self {
+        match environment {
+            LatexTable::Tabular => LatexEnvironment::Tabular,
+            LatexTable::Longtable => LatexEnvironment::Longtable,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct QueryArgs {
+    /// The die being rolled.
+    die: DieName,
+
+    /// The difficulty class to beat.
+    #[arg(long)]
+    dc: u32,
+
+    /// The number of turbo tokens available.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// Print the probability as an exact fraction instead of a rounded percentage.
+    #[arg(long)]
+    exact: bool,
+
+    #[command(flatten)]
+    modifier: ModifierArgs,
+
+    #[command(flatten)]
+    advantage: AdvantageArgs,
+}
+
+#[derive(Debug, Default, Args)]
+struct ModifierArgs {
+    /// A flat modifier added to the roll, e.g. `2` or `-1`.
+    #[arg(long, allow_negative_numbers = true, default_value_t = 0)]
+    modifier: i32,
+
+    /// Whether the modifier counts toward exploding the first die, or only toward the total.
+    #[arg(long, value_enum, default_value_t = ModifierOn::Total)]
+    modifier_rule: ModifierOn,
+}
+
+impl ModifierArgs {
+    fn modifier(&self) -> Modifier {
+        let rule = match self.modifier_rule {
+            ModifierOn::Total => ModifierRule::TotalOnly,
+            ModifierOn::Explosion => ModifierRule::CountsTowardExplosion,
         };
-        write!(f, "{}", s)
+        Modifier::new(self.modifier, rule)
     }
 }
 
-impl Die {
-    /// Returns the number of sides on the die.
-    fn sides(self) -> u32 {
-        match self {
-            Die::D4 => 4,
-            Die::D6 => 6,
-            Die::D8 => 8,
-            Die::D10 => 10,
-            Die::D12 => 12,
-            Die::D20 => 20,
+#[derive(Debug, Default, Args)]
+struct AdvantageArgs {
+    /// Roll twice and keep the higher total.
+    #[arg(long, conflicts_with_all = ["disadvantage", "modifier"])]
+    advantage: bool,
+
+    /// Roll twice and keep the lower total.
+    #[arg(long, conflicts_with = "modifier")]
+    disadvantage: bool,
+}
+
+impl AdvantageArgs {
+    fn mode(&self) -> RollMode {
+        match (self.advantage, self.disadvantage) {
+            (true, _) => RollMode::Advantage,
+            (_, true) => RollMode::Disadvantage,
+            _ => RollMode::Normal,
         }
     }
+}
+
+/// How a modifier interacts with exploding dice, as chosen on the command line.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+enum ModifierOn {
+    /// The modifier is only added to the final total.
+    #[default]
+    Total,
+
+    /// The modifier is added to the first die, and counts toward exploding it.
+    Explosion,
+}
+
+#[derive(Debug, Args)]
+struct StatsArgs {
+    /// The die being rolled.
+    die: DieName,
+
+    /// The number of turbo tokens available.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// Comma-separated list of additional percentiles to print, between 0 and 100.
+    #[arg(long, value_delimiter = ',')]
+    percentiles: Vec<f64>,
+}
+
+#[derive(Debug, Args)]
+struct DcArgs {
+    /// The desired probability of success, either as a fraction (e.g. `0.5`) or as a percentage
+    /// (e.g. `50%`).
+    #[arg(long, value_parser = parse_probability)]
+    target: f64,
+
+    /// Comma-separated list of dice to include, e.g. `d4,d8`. Defaults to every die on the ladder.
+    #[arg(long, value_delimiter = ',')]
+    dice: Vec<DieName>,
+
+    /// The number of turbo tokens available.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+}
+
+#[derive(Debug, Args)]
+struct PolicyArgs {
+    /// The die being rolled.
+    die: DieName,
+
+    /// The difficulty class to beat.
+    #[arg(long)]
+    dc: u32,
+
+    /// The number of turbo tokens available.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// The value of each turbo token left over after the last check, relative to the value of a
+    /// successful check.
+    #[arg(long, default_value_t = 0.0)]
+    token_value: f64,
+
+    /// A future check to make after this one, written as `die:dc`, e.g. `d8:12`. Can be repeated.
+    #[arg(long = "then")]
+    future_checks: Vec<Check<DieName>>,
+}
+
+#[derive(Debug, Args)]
+struct SimulateArgs {
+    /// Comma-separated list of dice to include, e.g. `d4,d8`. Defaults to every die on the ladder.
+    #[arg(long, value_delimiter = ',')]
+    dice: Vec<DieName>,
+
+    /// The DCs to simulate, e.g. `12`, `1..20`, or `1..=20`.
+    #[arg(long, value_parser = parse_range, default_value = "1..=40")]
+    dc: RangeInclusive<u32>,
+
+    /// The number of turbo tokens available.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// The number of rolls to simulate for each die and DC, at least 1.
+    #[arg(long, default_value_t = 100_000, value_parser = clap::value_parser!(u64).range(1..))]
+    trials: u64,
+
+    /// The seed for the random number generator.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Compare the estimates against the analytic probabilities.
+    #[arg(long)]
+    compare: bool,
+}
+
+#[derive(Debug, Args)]
+struct RollArgs {
+    /// The die being rolled.
+    die: DieName,
+
+    /// The difficulty class to beat. Turbo tokens are only spent when necessary to beat it.
+    /// Without a DC, turbo tokens are spent whenever they can explode the die.
+    #[arg(long)]
+    dc: Option<u32>,
+
+    /// The number of turbo tokens available.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// The seed for the random number generator. Defaults to a random seed.
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+struct SessionArgs {
+    /// The file the session is saved in. It is created with the ladder given by `--ladder` if it
+    /// does not exist.
+    #[arg(long, default_value = "session.json")]
+    file: PathBuf,
+
+    #[command(subcommand)]
+    command: SessionCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SessionCommand {
+    /// Print every player's skill dice and turbo tokens.
+    Show,
+
+    /// Add a player with no skills and no turbo tokens.
+    AddPlayer {
+        /// The player's name.
+        name: String,
+    },
+
+    /// Remove a player.
+    RemovePlayer {
+        /// The player's name.
+        name: String,
+    },
+
+    /// Set the die of one of a player's skills.
+    SetSkill {
+        /// The player's name.
+        player: String,
+
+        /// The name of the skill.
+        skill: String,
+
+        /// The skill's die.
+        die: DieName,
+    },
+
+    /// Set the number of turbo tokens a player holds.
+    SetTokens {
+        /// The player's name.
+        player: String,
+
+        /// The number of turbo tokens.
+        tokens: u32,
+    },
+
+    /// Have a player make a check, awarding a turbo token on failure.
+    Check {
+        /// The player's name.
+        player: String,
+
+        /// The name of the skill.
+        skill: String,
+
+        /// The difficulty class to beat.
+        #[arg(long)]
+        dc: u32,
+
+        /// How to decide whether to spend turbo tokens.
+        #[arg(long, value_enum, default_value_t = Spend::Always)]
+        spend: Spend,
+
+        /// The seed for the random number generator. Defaults to a random seed.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+/// Ways of deciding whether to spend turbo tokens during a check.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Spend {
+    /// Spend turbo tokens whenever they beat the DC or explode a die that must explode.
+    Always,
+
+    /// Never spend turbo tokens.
+    Never,
+
+    /// Ask before spending turbo tokens.
+    Ask,
+}
+
+#[derive(Debug, Args)]
+struct ProgressionArgs {
+    /// The skill's die at the start of the campaign.
+    die: DieName,
+
+    /// Comma-separated list of the DCs of the checks, repeated in order until every check is made,
+    /// e.g. `8,12,20`.
+    #[arg(long, value_delimiter = ',', required = true)]
+    dc: Vec<u32>,
+
+    /// The number of checks in the campaign. Defaults to one check per DC.
+    #[arg(long)]
+    checks: Option<usize>,
+
+    /// When the skill's die advances to the next die on the ladder.
+    #[arg(long, value_enum, default_value_t = AdvanceOn::Explosion)]
+    advance: AdvanceOn,
+
+    /// The number of turbo tokens available at each check.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// Print probabilities as exact fractions instead of rounded percentages.
+    #[arg(long)]
+    exact: bool,
+}
+
+#[derive(Debug, Args)]
+struct EconomyArgs {
+    /// The die rolled for each check.
+    die: DieName,
+
+    /// Comma-separated list of the DCs of the checks in the schedule, e.g. `8,12,20`.
+    #[arg(long, value_delimiter = ',', required = true)]
+    dc: Vec<u32>,
+
+    /// The number of turbo tokens held before the first check.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
+
+    /// The most turbo tokens a player can hold. Tokens earned beyond this are not kept.
+    #[arg(long, default_value_t = 20)]
+    max_tokens: u32,
+}
+
+#[derive(Debug, Args)]
+struct ContestArgs {
+    /// The die rolled by the first side.
+    #[arg(required_unless_present = "matrix")]
+    die: Option<DieName>,
+
+    /// The die rolled by the second side.
+    #[arg(required_unless_present = "matrix")]
+    opponent: Option<DieName>,
+
+    /// The number of turbo tokens available to the first side.
+    #[arg(long, default_value_t = 0)]
+    tokens: u32,
 
-    /// Returns the next highest die type, saturating at a d20.
-    fn next(self) -> Die {
-        match self {
-            Die::D4 => Die::D6,
-            Die::D6 => Die::D8,
-            Die::D8 => Die::D10,
-            Die::D10 => Die::D12,
-            Die::D12 => Die::D20,
-            Die::D20 => Die::D20,
+    /// The number of turbo tokens available to the second side.
+    #[arg(long, default_value_t = 0)]
+    opponent_tokens: u32,
+
+    /// Print the matchup matrix of every pair of dice instead of a single contest.
+    #[arg(long, conflicts_with_all = ["die", "opponent"])]
+    matrix: bool,
+
+    /// Comma-separated list of dice to include in the matrix, e.g. `d4,d8`. Defaults to every die
+    /// on the ladder.
+    #[arg(long, value_delimiter = ',', requires = "matrix")]
+    dice: Vec<DieName>,
+}
+
+#[derive(Debug, Args)]
+struct GroupArgs {
+    /// Each party member's die and turbo tokens, written as `die:tokens` (e.g. `d8:2`), or just
+    /// `die` for a member with no turbo tokens.
+    #[arg(required = true)]
+    members: Vec<Member<DieName>>,
+
+    /// The DCs to compute, e.g. `12`, `1..20`, or `1..=20`.
+    #[arg(long, value_parser = parse_range)]
+    dc: RangeInclusive<u32>,
+
+    /// The number of members who must succeed, up to the size of the party. Defaults to at least
+    /// half of the party.
+    #[arg(long)]
+    at_least: Option<usize>,
+
+    /// Print probabilities as exact fractions instead of rounded percentages.
+    #[arg(long)]
+    exact: bool,
+}
+
+#[derive(Debug, Args)]
+struct PoolArgs {
+    /// Each player's check, in order, written as `die:dc`, e.g. `d8:12`.
+    #[arg(required = true)]
+    checks: Vec<Check<DieName>>,
+
+    /// The number of turbo tokens in the shared pool before the first check. Tokens a player does
+    /// not spend, and tokens granted for failing, go back into the pool for later checks.
+    #[arg(long)]
+    tokens: u32,
+
+    /// Print probabilities as exact fractions instead of rounded percentages.
+    #[arg(long)]
+    exact: bool,
+}
+
+/// When a skill's die advances, as chosen on the command line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum AdvanceOn {
+    /// Advance whenever the skill's die explodes.
+    Explosion,
+
+    /// Advance whenever a check with the skill succeeds.
+    Success,
+}
+
+impl From<AdvanceOn> for Advancement {
+    fn from(advance: AdvanceOn) -> Self {
+        match advance {
+            AdvanceOn::Explosion => Advancement::OnExplosion,
+            AdvanceOn::Success => Advancement::OnSuccess,
         }
     }
 }
 
-/// Computes the probability of beating a given difficulty class when starting with the given die
-/// type. Turbo tokens are not considered in this function.
-///
-/// In Never Stop Blowing Up, each player begins with each of their skills at a d4. When making a
-/// check, the player rolls the die associated with the skill. If the result is the maximum value
-/// for the die, the die explodes, and the player upgrades to the next highest die type and rolls
-/// again, adding the result to the maximum of the previous die. This explosion process can
-/// continue up to a d20, at which point the dice cannot explode any further, and the player will
-/// reroll the d20 until they no longer roll the maximum value.
-///
-/// # Arguments
-///
-/// * `die` - The type of die being rolled.
-/// * `dc` - The difficulty class to beat.
-fn probability_of_success(die: Die, dc: u32) -> f64 {
-    // Can always roll a 1 or higher.
-    if dc <= 1 {
-        return 1.0;
+/// A die named on the command line, such as `d8`, before it is looked up on the ladder.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct DieName(u32);
+
+impl FromStr for DieName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .trim_start_matches(['d', 'D'])
+            .parse()
+            .map(DieName)
+            .map_err(|_| format!("invalid die `{}`", s))
     }
+}
 
-    // If the DC is lower than or equal to the maximum value of the die.
-    if dc <= die.sides() {
-        return (die.sides() - dc + 1) as f64 // # of successful outcomes
-            / die.sides() as f64; // # of total outcomes
+impl DieName {
+    /// Looks up the die on the given ladder, exiting with an error if it is not on the ladder.
+    fn resolve(self, ladder: &Ladder) -> Rung<'_> {
+        ladder.rung_with_sides(self.0).unwrap_or_else(|| {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("d{} is not on the ladder `{}`", self.0, ladder),
+                )
+                .exit()
+        })
     }
+}
 
-    // If the DC is higher than the maximum value of the die, explode the die and recurse.
-    let p = 1.0 / die.sides() as f64; // Probability of exploding.
-    p * probability_of_success(die.next(), dc - die.sides())
+/// Looks up each of the given dice on the ladder, or returns every die on the ladder if none are
+/// given.
+fn resolve_dice<'a>(ladder: &'a Ladder, dice: &[DieName]) -> Vec<Rung<'a>> {
+    if dice.is_empty() {
+        ladder.rungs().collect()
+    } else {
+        dice.iter().map(|die| die.resolve(ladder)).collect()
+    }
 }
 
-/// Computes the probability of beating a given difficulty class when starting with the given die
-/// type and using available turbo tokens to explode the die.
-///
-/// When a player fails an ability check, they receieve a turbo token. Turbo tokens can be spent
-/// on any later roll to add 1 to the result of their roll for each token spent. Players can use
-/// this mechanic to explode their die if they have enough turbo tokens to increase their roll to
-/// the max value of the die.
-///
-/// This function assumes that a player will always spend a turbo token to explode the die if they
-/// can.
-///
-/// # Arguments
+/// Parses a range of integers, written either as a single integer, as `start..end`, or as
+/// `start..=end`.
+fn parse_range(s: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |s: &str| s.trim().parse::<u32>().map_err(|e| format!("invalid bound `{}`: {}", s, e));
+
+    let range = if let Some((start, end)) = s.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = s.split_once("..") {
+        let end = parse(end)?
+            .checked_sub(1)
+            .ok_or_else(|| format!("range `{}` is empty", s))?;
+        parse(start)?..=end
+    } else {
+        let n = parse(s)?;
+        n..=n
+    };
+
+    if range.is_empty() {
+        return Err(format!("range `{}` is empty", s));
+    }
+    Ok(range)
+}
+
+/// Parses a probability, written either as a fraction between 0 and 1 or as a percentage ending in
+/// `%`.
+fn parse_probability(s: &str) -> Result<f64, String> {
+    let (number, scale) = match s.trim().strip_suffix('%') {
+        Some(percent) => (percent, 100.0),
+        None => (s.trim(), 1.0),
+    };
+    let p = number
+        .trim()
+        .parse::<f64>()
+        .map_err(|e| format!("invalid probability `{}`: {}", s, e))?
+        / scale;
+
+    if !(p > 0.0 && p <= 1.0) {
+        return Err(format!("probability `{}` must be greater than 0% and at most 100%", s));
+    }
+    Ok(p)
+}
+
+/// Prints a table of probabilities for each DC and die type, for each number of turbo tokens, with
+/// the probabilities computed in the number type `P`.
 ///
-/// * `die` - The type of die being rolled.
-/// * `turbo_tokens` - The number of turbo tokens available to the player.
-/// * `dc` - The difficulty class to beat.
-fn probability_of_success_with_turbo_tokens(die: Die, turbo_tokens: u32, dc: u32) -> f64 {
-    // Can always roll a 1 or higher.
-    if dc <= 1 {
-        return 1.0;
-    }
-
-    // If the DC is lower than or equal to the maximum value of the die.
-    if dc <= die.sides() {
-        // Turbo token can be counted as a successful outcome.
-        return (die.sides() - dc + 1 + turbo_tokens).min(die.sides()) as f64 // # of successful outcomes
-            / die.sides() as f64; // # of total outcomes
-    }
-
-    // If the DC is higher than the maximum value of the die, explode the die and recurse.
-    (1..=die.sides())
-        .map(|roll| { // Consider all possible rolls with the current die.
-            if roll + turbo_tokens < die.sides() {
-                // The die cannot explode, even with using all turbo tokens.
-                return 0.0;
+/// Markdown tables show probabilities with `format`, while CSV and JSON output show them with
+/// `raw` and `value` respectively.
+fn print_tables<P: Probability>(
+    ladder: &Ladder,
+    args: &TableArgs,
+    format: impl Fn(&P) -> String,
+    raw: fn(&P) -> String,
+    value: fn(&P) -> Value,
+) {
+    let tables = args.tables.build::<P>(ladder);
+
+    match args.format {
+        Format::Markdown => print!("{}", output::markdown(&tables, format)),
+        Format::Csv => print!("{}", output::csv(&tables, raw)),
+        Format::Json => println!("{:#}", output::json(&tables, value)),
+        Format::Latex => {
+            print!("{}", output::latex(&tables, format, args.latex_environment.into()));
+        },
+    }
+}
+
+fn table(ladder: &Ladder, args: &TableArgs) {
+    if args.exact {
+        print_tables::<Exact>(ladder, args, format_fraction, format_fraction, |p| json!(p.to_string()));
+    } else {
+        let format = |p: &f64| format!("{:.*}%", args.precision, p * 100.0);
+        print_tables::<f64>(ladder, args, format, f64::to_string, |&p| json!(p));
+    }
+}
+
+fn query(ladder: &Ladder, args: &QueryArgs) {
+    let die = args.die.resolve(ladder);
+    if args.exact {
+        println!("{}", format_fraction(&query_in::<Exact>(die, args)));
+    } else {
+        println!("{}", format_percent(&query_in::<f64>(die, args)));
+    }
+}
+
+fn query_in<P: Probability>(die: Rung<'_>, args: &QueryArgs) -> P {
+    match args.advantage.mode() {
+        RollMode::Normal => {
+            let modifier = args.modifier.modifier();
+            probability_of_success_with_modifier_in(die, args.tokens, args.dc, modifier)
+        },
+        mode => probability_of_success_with_advantage_in(die, args.tokens, args.dc, mode),
+    }
+}
+
+fn stats(ladder: &Ladder, args: &StatsArgs) {
+    let statistics = statistics(args.die.resolve(ladder), args.tokens);
+
+    println!("mean: {:.6}", statistics.mean);
+    println!("variance: {:.6}", statistics.variance);
+    println!("standard deviation: {:.6}", statistics.standard_deviation);
+    println!("median: {}", statistics.median);
+    for &percentile in &args.percentiles {
+        match statistics.percentile(percentile / 100.0) {
+            Some(total) => println!("{}th percentile: {}", percentile, total),
+            None => println!("{}th percentile: out of range", percentile),
+        }
+    }
+}
+
+fn dc(ladder: &Ladder, args: &DcArgs) {
+    for die in resolve_dice(ladder, &args.dice) {
+        match highest_dc_with_probability(die, args.tokens, args.target) {
+            Some(dc) => println!("{}: DC {}", die, dc),
+            None => println!("{}: no DC", die),
+        }
+    }
+}
+
+fn policy(ladder: &Ladder, args: &PolicyArgs) {
+    let die = args.die.resolve(ladder);
+    let checks = std::iter::once(Check { die, dc: args.dc })
+        .chain(args.future_checks.iter().map(|check| Check {
+            die: check.die.resolve(ladder),
+            dc: check.dc,
+        }))
+        .collect();
+    let mut solver = Solver::new(checks, args.token_value);
+
+    let always_spend = probability_of_success_with_turbo_tokens_in::<f64>(die, args.tokens, args.dc);
+    println!("expected value: {:.6}", solver.expected_value(0, args.tokens));
+    println!("success probability: {}", format_percent(&solver.success_probability(args.tokens)));
+    println!("success probability if always spending: {}", format_percent(&always_spend));
+    println!();
+
+    let explodes = die.explode().is_some();
+    for roll in 1..=die.sides() {
+        let action = match solver.action(0, die, args.tokens, args.dc, roll) {
+            Action::Keep if roll >= args.dc => "keep (success)".to_string(),
+            Action::Keep if explodes && roll == die.sides() => "keep (explodes)".to_string(),
+            Action::Keep => "keep (failure)".to_string(),
+            Action::Spend(tokens) if explodes && args.dc > die.sides() => {
+                format!("spend {} to explode", tokens)
+            },
+            Action::Spend(tokens) => format!("spend {} to succeed", tokens),
+        };
+        println!("roll {}: {}", roll, action);
+    }
+}
+
+fn simulate(ladder: &Ladder, args: &SimulateArgs) {
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let mut table = Builder::default();
+
+    let mut header = vec!["Die", "DC", "Simulated", "95% CI"];
+    if args.compare {
+        header.extend(["Analytic", "Difference", "Within CI"]);
+    }
+    table.push_record(header);
+
+    for die in resolve_dice(ladder, &args.dice) {
+        for dc in args.dc.clone() {
+            let estimate = exploding::simulate(die, args.tokens, dc, args.trials, &mut rng);
+            let (low, high) = estimate.confidence_interval(Z_95);
+
+            let mut record = vec![
+                die.to_string(),
+                dc.to_string(),
+                format_percent(&estimate.probability()),
+                format!("{} – {}", format_percent(&low), format_percent(&high)),
+            ];
+            if args.compare {
+                let analytic = probability_of_success_with_turbo_tokens_in::<f64>(die, args.tokens, dc);
+                let within = (low..=high).contains(&analytic);
+                record.extend([
+                    format_percent(&analytic),
+                    format_percent(&(estimate.probability() - analytic)),
+                    if within { "yes" } else { "no" }.to_string(),
+                ]);
             }
+            table.push_record(record);
+        }
+    }
 
-            // Die will explode (it must for a chance to beat the DC).
-            let tokens_needed_to_explode = die.sides() - roll;
-            probability_of_success_with_turbo_tokens(die.next(), turbo_tokens - tokens_needed_to_explode, dc - die.sides())
-                / die.sides() as f64
-        })
-        .sum()
+    println!("{}", table.build().with(Style::markdown()));
 }
 
-fn main() {
-    // Generate table of probabilities for each DC and die type.
-    let max_dc = 80;
-    let max_turbo_tokens = 5;
-    let dice = [Die::D4, Die::D6, Die::D8, Die::D10, Die::D12, Die::D20];
-    let style = Style::markdown();
+fn roll(ladder: &Ladder, args: &RollArgs) {
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let roll = exploding::roll(args.die.resolve(ladder), args.tokens, args.dc, &mut rng);
 
-    for turbo_tokens in 0..=max_turbo_tokens {
-        let mut table = Builder::default();
+    println!("{}", roll);
+    if args.tokens > 0 {
+        let spent = roll.tokens_spent();
+        println!("turbo tokens spent: {} ({} left)", spent, args.tokens - spent);
+    }
+    if let Some(dc) = args.dc {
+        let result = if roll.total() >= dc { "success" } else { "failure" };
+        println!("DC {}: {}", dc, result);
+    }
+}
 
-        let header = std::iter::once("DC".to_string())
+/// Asks on the terminal whether to spend turbo tokens on the given offer.
+fn ask(offer: &Offer<Rung<'_>>) -> bool {
+    let purpose = match offer.purpose {
+        Purpose::Succeed => "beat the DC",
+        Purpose::Explode => "explode the die",
+    };
+    print!(
+        "rolled {} on {} (total so far {}). Spend {} of {} to {}? [y/N] ",
+        offer.result,
+        offer.die,
+        offer.total,
+        offer.cost,
+        output::turbo_tokens(offer.turbo_tokens),
+        purpose,
+    );
+    std::io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).ok();
+    matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+fn session(ladder: &Ladder, args: &SessionArgs) -> Result<(), SessionError> {
+    let mut session = if args.file.exists() {
+        Session::load(&args.file)?
+    } else {
+        Session::new(ladder.clone())
+    };
+
+    match &args.command {
+        SessionCommand::Show => {
+            println!("ladder: {}", session.ladder);
+            for (name, player) in &session.players {
+                println!();
+                println!("{} ({})", name, output::turbo_tokens(player.turbo_tokens));
+                for (skill, sides) in &player.skills {
+                    println!("  {}: d{}", skill, sides);
+                }
+            }
+            return Ok(());
+        },
+        SessionCommand::AddPlayer { name } => session.add_player(name)?,
+        SessionCommand::RemovePlayer { name } => {
+            session.remove_player(name)?;
+        },
+        SessionCommand::SetSkill { player, skill, die } => session.set_skill(player, skill, die.0)?,
+        SessionCommand::SetTokens { player, tokens } => {
+            session.player_mut(player)?.turbo_tokens = *tokens;
+        },
+        SessionCommand::Check { player, skill, dc, spend, seed } => {
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(*seed),
+                None => StdRng::from_entropy(),
+            };
+            let decide = |offer: &Offer<Rung<'_>>| match spend {
+                Spend::Always => true,
+                Spend::Never => false,
+                Spend::Ask => ask(offer),
+            };
+
+            let result = session.check(player, skill, *dc, &mut rng, decide)?;
+            println!("{}", result.roll);
+            println!("DC {}: {}", dc, if result.success { "success" } else { "failure" });
+            println!("{} now holds {}", player, output::turbo_tokens(result.turbo_tokens));
+        },
+    }
+
+    session.save(&args.file)
+}
+
+fn print_progression<P: Probability>(
+    ladder: &Ladder,
+    args: &ProgressionArgs,
+    format: fn(&P) -> String,
+    raw: fn(&P) -> String,
+) {
+    let checks = args.checks.unwrap_or(args.dc.len());
+    let dcs = args.dc.iter().copied().cycle().take(checks).collect::<Vec<_>>();
+    let progression = Progression::<P, _>::new(
+        args.die.resolve(ladder),
+        args.advance.into(),
+        args.tokens,
+        &dcs,
+    );
+
+    let mut table = Builder::default();
+    table.push_record(["Die", "Probability"]);
+    for (die, p) in progression.dice.iter().zip(progression.final_distribution()) {
+        table.push_record([die.to_string(), format(p)]);
+    }
+    println!("{}", table.build().with(Style::markdown()));
+    println!();
+    println!("expected successes: {}", raw(&progression.expected_successes()));
+    println!("success rate: {}", format(&progression.success_rate()));
+}
+
+fn progression(ladder: &Ladder, args: &ProgressionArgs) {
+    if args.exact {
+        print_progression::<Exact>(ladder, args, format_fraction, format_fraction);
+    } else {
+        print_progression::<f64>(ladder, args, format_percent, |p| format!("{:.6}", p));
+    }
+}
+
+fn economy(ladder: &Ladder, args: &EconomyArgs) {
+    if args.tokens > args.max_tokens {
+        Cli::command()
+            .error(ErrorKind::ValueValidation, "--tokens cannot be greater than --max-tokens")
+            .exit();
+    }
+
+    let die = args.die.resolve(ladder);
+    let economy = TokenEconomy::new(die, &args.dc, args.max_tokens, args.tokens);
+
+    let mut table = Builder::default();
+    let header = std::iter::once("Tokens".to_string())
+        .chain(economy.dcs.iter().map(|dc| format!("Before DC {}", dc)));
+    table.push_record(header);
+    for tokens in 0..=economy.max_tokens as usize {
+        let probabilities = economy.distributions
+            .iter()
+            .map(|distribution| format_percent(&distribution[tokens]));
+        table.push_record(std::iter::once(tokens.to_string()).chain(probabilities));
+    }
+    println!("{}", table.build().with(Style::markdown()));
+    println!();
+
+    let expected_tokens = economy.expected_tokens();
+    for (i, &dc) in economy.dcs.iter().enumerate() {
+        println!(
+            "DC {}: {} success, {:.6} turbo tokens held on average",
+            dc,
+            format_percent(&economy.success_probabilities[i]),
+            expected_tokens[i],
+        );
+    }
+    println!("long-run success rate: {}", format_percent(&economy.success_rate()));
+}
+
+fn contest(ladder: &Ladder, args: &ContestArgs) {
+    let (Some(die), Some(opponent)) = (args.die, args.opponent) else {
+        return contest_matrix(ladder, args);
+    };
+    let (die, opponent) = (die.resolve(ladder), opponent.resolve(ladder));
+
+    let contest = exploding::contest(die, args.tokens, opponent, args.opponent_tokens);
+    // Name each side by position as well as die, since both sides may roll the same die.
+    let first = Member { die, turbo_tokens: args.tokens };
+    let second = Member { die: opponent, turbo_tokens: args.opponent_tokens };
+    println!("first side ({}) wins: {}", first, format_percent(&contest.win));
+    println!("tie: {}", format_percent(&contest.tie));
+    println!("second side ({}) wins: {}", second, format_percent(&contest.loss));
+}
+
+fn contest_matrix(ladder: &Ladder, args: &ContestArgs) {
+    let dice = resolve_dice(ladder, &args.dice);
+    let contests = dice
+        .iter()
+        .map(|&die| {
+            dice.iter()
+                .map(|&opponent| exploding::contest(die, args.tokens, opponent, args.opponent_tokens))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // One matrix of the row's die beating the column's die, and one of the two tying.
+    let matrix = |title: &str, outcome: fn(&Contest) -> &f64| {
+        let mut table = Builder::default();
+        let header = std::iter::once(format!("{} tokens vs. {}", args.tokens, args.opponent_tokens))
             .chain(dice.iter().map(|die| die.to_string()));
         table.push_record(header);
+        for (die, row) in dice.iter().zip(&contests) {
+            let probabilities = row.iter().map(|contest| format_percent(outcome(contest)));
+            table.push_record(std::iter::once(die.to_string()).chain(probabilities));
+        }
+        format!("## {}\n\n{}\n", title, table.build().with(Style::markdown()))
+    };
 
-        (1..=max_dc)
-            .map(|dc| {
-                let probabilties = dice
-                    .into_iter()
-                    .map(move |die| probability_of_success_with_turbo_tokens(die, turbo_tokens, dc))
-                    .map(|p| format!("{:.6}%", p * 100.0));
-                std::iter::once(dc.to_string()).chain(probabilties)
-            })
-            .for_each(|record| table.push_record(record));
+    println!("{}", matrix("Row wins", |contest| &contest.win));
+    print!("{}", matrix("Tie", |contest| &contest.tie));
+}
+
+fn print_group<P: Probability>(ladder: &Ladder, args: &GroupArgs, format: fn(&P) -> String) {
+    let members = args.members
+        .iter()
+        .map(|member| Member {
+            die: member.die.resolve(ladder),
+            turbo_tokens: member.turbo_tokens,
+        })
+        .collect::<Vec<_>>();
+    let at_least = args.at_least.unwrap_or(members.len().div_ceil(2));
+
+    let mut table = Builder::default();
+    table.push_record([
+        "DC".to_string(),
+        "All succeed".to_string(),
+        format!("At least {} succeed", at_least),
+        "Best roll succeeds".to_string(),
+    ]);
+    for dc in args.dc.clone() {
+        let group = GroupCheck::<P>::new(&members, dc);
+        table.push_record([
+            dc.to_string(),
+            format(&group.all()),
+            format(&group.at_least(at_least)),
+            format(&group.best()),
+        ]);
+    }
+    println!("{}", table.build().with(Style::markdown()));
+}
+
+fn group(ladder: &Ladder, args: &GroupArgs) {
+    if args.at_least.is_some_and(|at_least| at_least > args.members.len()) {
+        Cli::command()
+            .error(
+                ErrorKind::ValueValidation,
+                format!("--at-least cannot be greater than the party size of {}", args.members.len()),
+            )
+            .exit();
+    }
+
+    if args.exact {
+        print_group::<Exact>(ladder, args, format_fraction);
+    } else {
+        print_group::<f64>(ladder, args, format_percent);
+    }
+}
+
+fn print_pool<P: Probability>(
+    ladder: &Ladder,
+    args: &PoolArgs,
+    format: fn(&P) -> String,
+    raw: fn(&P) -> String,
+) {
+    let checks = args.checks
+        .iter()
+        .map(|check| Check {
+            die: check.die.resolve(ladder),
+            dc: check.dc,
+        })
+        .collect::<Vec<_>>();
+    let allocation = allocate_in::<P, _>(&checks, args.tokens);
+
+    let mut table = Builder::default();
+    table.push_record(["Check", "Success", "Pool before (expected)"]);
+    for (i, (check, p)) in allocation.checks.iter().zip(&allocation.probabilities).enumerate() {
+        table.push_record([check.to_string(), format(p), raw(&allocation.expected_pool(i))]);
+    }
+    println!("{}", table.build().with(Style::markdown()));
+    println!();
+    println!("expected successes: {}", raw(&allocation.expected_successes()));
+    println!("expected tokens left in the pool: {}", raw(&allocation.expected_pool(checks.len())));
+    println!();
+
+    // How many tokens to give each player for every pool size they can face.
+    let max_pool = args.tokens as usize + checks.len() - 1;
+    let mut policy = Builder::default();
+    policy.push_record(
+        std::iter::once("Tokens to give".to_string())
+            .chain((0..=max_pool).map(|pool| format!("Pool of {}", pool))),
+    );
+    for (i, check) in allocation.checks.iter().enumerate() {
+        policy.push_record(std::iter::once(check.to_string()).chain((0..=max_pool).map(|pool| {
+            match allocation.pools[i].get(pool) {
+                Some(p) if *p > P::zero() => allocation.turbo_tokens[i][pool].to_string(),
+                _ => String::new(),
+            }
+        })));
+    }
+    println!("{}", policy.build().with(Style::markdown()));
+}
+
+fn pool(ladder: &Ladder, args: &PoolArgs) {
+    if args.exact {
+        print_pool::<Exact>(ladder, args, format_fraction, format_fraction);
+    } else {
+        print_pool::<f64>(ladder, args, format_percent, |p| format!("{:.6}", p));
+    }
+}
+
+fn chart(ladder: &Ladder, args: &ChartArgs) -> std::io::Result<()> {
+    std::fs::create_dir_all(&args.output_dir)?;
+    for table in args.tables.build::<f64>(ladder) {
+        let path = args.output_dir.join(format!("{}-turbo-tokens.svg", table.turbo_tokens));
+        std::fs::write(&path, output::svg(&table))?;
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn heatmap(ladder: &Ladder, args: &HeatmapArgs) -> std::io::Result<()> {
+    let tables = args.tables.build::<f64>(ladder);
+    let heatmap = match args.format {
+        HeatmapFormat::Svg => output::heatmap_svg(&tables),
+        HeatmapFormat::Html => output::heatmap_html(&tables),
+    };
+    match &args.output {
+        Some(path) => std::fs::write(path, heatmap),
+        None => {
+            print!("{}", heatmap);
+            Ok(())
+        },
+    }
+}
+
+fn report(ladder: &Ladder, args: &ReportArgs) -> std::io::Result<()> {
+    let tables = args.tables.build::<f64>(ladder);
+    std::fs::write(&args.output, output::html(&tables))?;
+    println!("wrote {}", args.output.display());
+    Ok(())
+}
+
+fn readme(ladder: &Ladder, args: &ReadmeArgs) -> Result<(), String> {
+    let path = args.path.display();
+    let readme = std::fs::read_to_string(&args.path)
+        .map_err(|err| format!("could not read {}: {}", path, err))?;
+
+    let missing = |marker| format!("{} does not contain the marker `{}`", path, marker);
+    let start = readme
+        .find(README_START)
+        .map(|start| start + README_START.len())
+        .ok_or_else(|| missing(README_START))?;
+    let end = readme[start..]
+        .find(README_END)
+        .map(|end| start + end)
+        .ok_or_else(|| missing(README_END))?;
 
-        let mut table = table.build();
-        table.with(style.clone());
+    let tables = TableArgs::default().tables.build::<f64>(ladder);
+    let updated = format!(
+        "{}\n\n{}{}",
+        &readme[..start],
+        output::markdown(&tables, format_percent),
+        &readme[end..],
+    );
 
-        println!("## {} turbo tokens", turbo_tokens);
-        println!();
-        println!("{}", table);
-        println!();
+    if args.check {
+        if updated != readme {
+            return Err(format!(
+                "the tables in {} are out of date; run `cargo run --release -- readme` to update \
+                 them",
+                path,
+            ));
+        }
+        println!("{} is up to date", path);
+    } else {
+        std::fs::write(&args.path, updated)
+            .map_err(|err| format!("could not write {}: {}", path, err))?;
+        println!("updated {}", path);
+    }
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Table(args)) => table(&cli.ladder, &args),
+        Some(Command::Query(args)) => query(&cli.ladder, &args),
+        Some(Command::Stats(args)) => stats(&cli.ladder, &args),
+        Some(Command::Dc(args)) => dc(&cli.ladder, &args),
+        Some(Command::Policy(args)) => policy(&cli.ladder, &args),
+        Some(Command::Simulate(args)) => simulate(&cli.ladder, &args),
+        Some(Command::Roll(args)) => roll(&cli.ladder, &args),
+        Some(Command::Session(args)) => {
+            if let Err(err) = session(&cli.ladder, &args) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Some(Command::Progression(args)) => progression(&cli.ladder, &args),
+        Some(Command::Economy(args)) => economy(&cli.ladder, &args),
+        Some(Command::Contest(args)) => contest(&cli.ladder, &args),
+        Some(Command::Group(args)) => group(&cli.ladder, &args),
+        Some(Command::Pool(args)) => pool(&cli.ladder, &args),
+        Some(Command::Chart(args)) => {
+            if let Err(err) = chart(&cli.ladder, &args) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Some(Command::Heatmap(args)) => {
+            if let Err(err) = heatmap(&cli.ladder, &args) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Some(Command::Report(args)) => {
+            if let Err(err) = report(&cli.ladder, &args) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Some(Command::Readme(args)) => {
+            if let Err(err) = readme(&cli.ladder, &args) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        None => table(&cli.ladder, &TableArgs::default()),
     }
 }
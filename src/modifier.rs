@@ -0,0 +1,187 @@
+use crate::{probability::with_turbo_tokens, ExplodingDie, Probability};
+use std::collections::HashMap;
+
+/// How a flat modifier interacts with exploding dice. Tables differ on this, so it is left as a
+/// rule variant.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ModifierRule {
+    /// The modifier is only added to the final total, and never helps a die explode.
+    #[default]
+    TotalOnly,
+
+    /// The modifier is added to the first die rolled, and counts toward reaching its maximum value
+    /// to explode it. A negative modifier can stop a die that rolled its maximum value from
+    /// exploding.
+    CountsTowardExplosion,
+}
+
+impl std::fmt::Display for ModifierRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ModifierRule::TotalOnly => write!(f, "total"),
+            ModifierRule::CountsTowardExplosion => write!(f, "explosion"),
+        }
+    }
+}
+
+/// A static bonus or penalty applied to a check, such as +2 from gear or -1 from a condition.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Modifier {
+    /// The amount added to the roll, which may be negative.
+    pub value: i32,
+
+    /// How the modifier interacts with exploding dice.
+    pub rule: ModifierRule,
+}
+
+impl Modifier {
+    /// Creates a modifier with the given value and rule.
+    pub fn new(value: i32, rule: ModifierRule) -> Self {
+        Modifier { value, rule }
+    }
+}
+
+impl std::fmt::Display for Modifier {
+    /// Formats the modifier with its sign, such as `+2` or `-1`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:+}", self.value)
+    }
+}
+
+/// Computes the probability of beating a given difficulty class with a flat modifier applied to
+/// the roll.
+///
+/// Turbo tokens are spent as described in
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens),
+/// treating the first die as showing its result plus the modifier if the modifier counts toward
+/// explosions.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `dc` - The difficulty class to beat.
+/// * `modifier` - The modifier applied to the roll.
+pub fn probability_of_success_with_modifier(
+    die: impl ExplodingDie,
+    turbo_tokens: u32,
+    dc: u32,
+    modifier: Modifier,
+) -> f64 {
+    probability_of_success_with_modifier_in(die, turbo_tokens, dc, modifier)
+}
+
+/// Same as [`probability_of_success_with_modifier`], but carries out the calculation in the given
+/// number type.
+pub fn probability_of_success_with_modifier_in<P: Probability, D: ExplodingDie>(
+    die: D,
+    turbo_tokens: u32,
+    dc: u32,
+    modifier: Modifier,
+) -> P {
+    let mut memo = HashMap::new();
+    with_modifier(die, turbo_tokens, dc, modifier, |die, turbo_tokens, dc| {
+        Some(with_turbo_tokens(die, turbo_tokens, dc, &mut memo))
+    })
+    .expect("every probability is computed")
+}
+
+/// Computes the probability of beating a DC with a modifier, looking up the probability of each
+/// unmodified roll that it depends on with `unmodified`.
+///
+/// Returns [`None`] if any of the lookups do.
+pub(crate) fn with_modifier<P: Probability, D: ExplodingDie>(
+    die: D,
+    turbo_tokens: u32,
+    dc: u32,
+    modifier: Modifier,
+    mut unmodified: impl FnMut(D, u32, u32) -> Option<P>,
+) -> Option<P> {
+    // Beating a DC with a modifier only added to the total is the same as beating a DC lowered by
+    // the modifier.
+    let shifted = (dc as i64 - modifier.value as i64).max(0) as u32;
+    if modifier.value == 0 || modifier.rule == ModifierRule::TotalOnly {
+        return unmodified(die, turbo_tokens, shifted);
+    }
+
+    // Otherwise, the modifier changes what the first die shows. Every die after it is unmodified.
+    let sides = die.sides();
+    let next = die.explode();
+    let dc = dc as i64;
+    let (sides_i, tokens_i) = (sides as i64, turbo_tokens as i64);
+    (1..=sides)
+        .map(|roll| {
+            let value = roll as i64 + modifier.value as i64;
+            let p = match next {
+                // The die explodes on its own, carrying its full value into the total.
+                Some(next) if value >= sides_i => {
+                    unmodified(next, turbo_tokens, (dc - value).max(0) as u32)?
+                },
+                _ if value >= dc => P::one(),
+
+                // Spend tokens to beat the DC directly if it is within reach of this die.
+                _ if next.is_none() || dc <= sides_i => {
+                    if value + tokens_i >= dc { P::one() } else { P::zero() }
+                },
+
+                // Otherwise, spend tokens to explode the die if possible.
+                Some(next) if value + tokens_i >= sides_i => {
+                    let spent = (sides_i - value) as u32;
+                    unmodified(next, turbo_tokens - spent, (dc - sides_i) as u32)?
+                },
+                _ => P::zero(),
+            };
+            Some(p / P::ratio(sides, 1))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{sweep, Case, MAX_DC, MAX_TOKENS},
+        Die,
+        Exact,
+        ProbabilityCube,
+    };
+
+    #[test]
+    fn total_only_lowers_dc() {
+        // The cube is checked against the recursion in its own tests, so look up both sides in it.
+        let cube = ProbabilityCube::<Exact>::new(&Die::ALL, MAX_TOKENS, MAX_DC + 2);
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            for value in [-2, 3] {
+                let modifier = Modifier::new(value, ModifierRule::TotalOnly);
+                let shifted = (dc as i32 - value).max(0) as u32;
+                assert_eq!(
+                    cube.probability_with_modifier(die, turbo_tokens, dc, modifier),
+                    cube.probability(die, turbo_tokens, shifted).cloned(),
+                    "{}, {}",
+                    case,
+                    modifier,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bonus_counts_toward_explosion() {
+        // With +1, a 3 or 4 on a d4 counts as 4 and explodes, which beats DC 5 with any roll of the
+        // next die. A 1 or 2 cannot reach 5 without tokens.
+        let modifier = Modifier::new(1, ModifierRule::CountsTowardExplosion);
+        let p: Exact = probability_of_success_with_modifier_in(Die::D4, 0, 5, modifier);
+        assert_eq!(p, Exact::ratio(1, 2));
+    }
+
+    #[test]
+    fn penalty_stops_explosion() {
+        // With -1, a 4 on a d4 only counts as 3, so it needs a token to explode. Nothing else can
+        // reach DC 5.
+        let modifier = Modifier::new(-1, ModifierRule::CountsTowardExplosion);
+        let p: Exact = probability_of_success_with_modifier_in(Die::D4, 0, 5, modifier);
+        assert_eq!(p, Exact::zero());
+        let p: Exact = probability_of_success_with_modifier_in(Die::D4, 1, 5, modifier);
+        assert_eq!(p, Exact::ratio(1, 4));
+    }
+}
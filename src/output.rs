@@ -0,0 +1,585 @@
+use crate::{ExplodingDie, Modifier, ModifierRule, Probability, RollMode, Table};
+use serde_json::{json, Value};
+
+/// Renders the given tables in markdown, each preceded by a heading naming its number of turbo
+/// tokens along with any modifier or advantage, formatting each probability with the given
+/// function.
+pub fn markdown<P: Probability, D: ExplodingDie>(
+    tables: &[Table<P, D>],
+    format: impl Fn(&P) -> String,
+) -> String {
+    tables
+        .iter()
+        .map(|table| format!("## {}\n\n{}\n\n", title(table), table.to_markdown(&format)))
+        .collect()
+}
+
+/// Renders the given tables as a single CSV document, formatting each probability with the given
+/// function.
+///
+/// The document has a header row of `turbo_tokens,modifier,modifier_rule,mode,dc` followed by the
+/// name of each die, and one row per DC per table. The modifier rule is `total` or `explosion`, and
+/// the mode is `normal`, `advantage` or `disadvantage`. All tables are expected to contain the same
+/// dice.
+pub fn csv<P: Probability, D: ExplodingDie>(
+    tables: &[Table<P, D>],
+    format: impl Fn(&P) -> String,
+) -> String {
+    let mut out = String::new();
+
+    if let Some(table) = tables.first() {
+        let header = ["turbo_tokens", "modifier", "modifier_rule", "mode", "dc"]
+            .map(String::from)
+            .into_iter()
+            .chain(table.dice.iter().map(|die| die.to_string()))
+            .collect::<Vec<_>>();
+        out.push_str(&header.join(","));
+        out.push('\n');
+    }
+
+    for table in tables {
+        for row in &table.rows {
+            let record = [
+                table.turbo_tokens.to_string(),
+                table.modifier.value.to_string(),
+                table.modifier.rule.to_string(),
+                table.mode.to_string(),
+                row.dc.to_string(),
+            ]
+            .into_iter()
+            .chain(row.probabilities.iter().map(&format))
+            .collect::<Vec<_>>();
+            out.push_str(&record.join(","));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Renders the given tables as a JSON array, converting each probability to a JSON value with the
+/// given function.
+///
+/// Each table is an object of the form:
+///
+/// ```json
+/// {
+///   "turbo_tokens": 0,
+///   "modifier": 0,
+///   "modifier_rule": "total",
+///   "mode": "normal",
+///   "dice": ["d4", "d6"],
+///   "rows": [{ "dc": 1, "probabilities": [1.0, 1.0] }]
+/// }
+/// ```
+pub fn json<P: Probability, D: ExplodingDie>(
+    tables: &[Table<P, D>],
+    value: impl Fn(&P) -> Value,
+) -> Value {
+    tables
+        .iter()
+        .map(|table| {
+            json!({
+                "turbo_tokens": table.turbo_tokens,
+                "modifier": table.modifier.value,
+                "modifier_rule": table.modifier.rule.to_string(),
+                "mode": table.mode.to_string(),
+                "dice": table.dice.iter().map(|die| die.to_string()).collect::<Vec<_>>(),
+                "rows": table.rows
+                    .iter()
+                    .map(|row| json!({
+                        "dc": row.dc,
+                        "probabilities": row.probabilities.iter().map(&value).collect::<Vec<_>>(),
+                    }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+/// The colors of the lines in a chart, cycled through in order.
+const COLORS: [&str; 8] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#17becf",
+];
+
+/// Renders a table as a standalone SVG line chart of the probability of success against the DC,
+/// with one line per die.
+pub fn svg<P: Probability, D: ExplodingDie>(table: &Table<P, D>) -> String {
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 500.0;
+    const LEFT: f64 = 70.0;
+    const RIGHT: f64 = 110.0;
+    const TOP: f64 = 50.0;
+    const BOTTOM: f64 = 60.0;
+    let (plot_width, plot_height) = (WIDTH - LEFT - RIGHT, HEIGHT - TOP - BOTTOM);
+
+    let first = table.rows.first().map_or(1, |row| row.dc);
+    let last = table.rows.last().map_or(1, |row| row.dc).max(first + 1);
+    let x = |dc: u32| LEFT + (dc - first) as f64 / (last - first) as f64 * plot_width;
+    let y = |p: f64| TOP + (1.0 - p) * plot_height;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"sans-serif\" font-size=\"12\">\n",
+        w = WIDTH,
+        h = HEIGHT,
+    );
+    out += &format!("<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n", WIDTH, HEIGHT);
+    out += &format!(
+        "<text x=\"{}\" y=\"30\" text-anchor=\"middle\" font-size=\"16\">{}</text>\n",
+        LEFT + plot_width / 2.0,
+        title(table),
+    );
+
+    // Horizontal grid lines every 10%.
+    for tenth in 0..=10 {
+        let y = y(tenth as f64 / 10.0);
+        out += &format!(
+            "<line x1=\"{}\" y1=\"{y:.2}\" x2=\"{}\" y2=\"{y:.2}\" stroke=\"#ddd\"/>\n",
+            LEFT,
+            LEFT + plot_width,
+            y = y,
+        );
+        out += &format!(
+            "<text x=\"{}\" y=\"{:.2}\" text-anchor=\"end\" dominant-baseline=\"middle\">{}%</text>\n",
+            LEFT - 8.0,
+            y,
+            tenth * 10,
+        );
+    }
+
+    // Vertical grid lines at round DCs, aiming for around ten of them.
+    let step = [1, 2, 5, 10, 20, 50, 100]
+        .into_iter()
+        .find(|&step| (last - first) / step <= 10)
+        .unwrap_or(((last - first) / 10).max(1));
+    for dc in (first..=last).filter(|dc| dc % step == 0) {
+        let x = x(dc);
+        out += &format!(
+            "<line x1=\"{x:.2}\" y1=\"{}\" x2=\"{x:.2}\" y2=\"{}\" stroke=\"#ddd\"/>\n",
+            TOP,
+            TOP + plot_height,
+            x = x,
+        );
+        out += &format!(
+            "<text x=\"{:.2}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            x,
+            TOP + plot_height + 18.0,
+            dc,
+        );
+    }
+
+    out += &format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\"/>\n",
+        LEFT, TOP, plot_width, plot_height,
+    );
+    out += &format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">DC</text>\n",
+        LEFT + plot_width / 2.0,
+        HEIGHT - 15.0,
+    );
+    out += &format!(
+        "<text x=\"20\" y=\"{y}\" text-anchor=\"middle\" transform=\"rotate(-90 20 {y})\">\
+         Probability of success</text>\n",
+        y = TOP + plot_height / 2.0,
+    );
+
+    // One line per die, with a legend entry to the right of the plot.
+    for (i, die) in table.dice.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        let points = table.rows
+            .iter()
+            .map(|row| format!("{:.2},{:.2}", x(row.dc), y(row.probabilities[i].to_f64())))
+            .collect::<Vec<_>>()
+            .join(" ");
+        out += &format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+            points, color,
+        );
+
+        let legend_y = TOP + 10.0 + i as f64 * 20.0;
+        let legend_x = LEFT + plot_width + 15.0;
+        out += &format!(
+            "<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+            legend_x,
+            legend_x + 20.0,
+            color,
+            y = legend_y,
+        );
+        out += &format!(
+            "<text x=\"{}\" y=\"{}\" dominant-baseline=\"middle\">{}</text>\n",
+            legend_x + 28.0,
+            legend_y,
+            die,
+        );
+    }
+
+    out += "</svg>\n";
+    out
+}
+
+/// Renders the given tables as a standalone SVG heatmap, with one column per DC and one row per
+/// die and turbo token count, each cell colored from red to green by its probability of success.
+///
+/// All tables are expected to contain the same dice and DCs.
+pub fn heatmap_svg<P: Probability, D: ExplodingDie>(tables: &[Table<P, D>]) -> String {
+    const CELL_WIDTH: f64 = 14.0;
+    const CELL_HEIGHT: f64 = 18.0;
+    const LEFT: f64 = 110.0;
+    const TOP: f64 = 60.0;
+
+    let rows = heatmap_rows(tables);
+    let dcs = tables.first().map(|table| table.rows.as_slice()).unwrap_or_default();
+    let width = LEFT + dcs.len() as f64 * CELL_WIDTH + 10.0;
+    let height = TOP + rows.len() as f64 * CELL_HEIGHT + 10.0;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"sans-serif\" font-size=\"10\">\n",
+        w = width,
+        h = height,
+    );
+    out += &format!("<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n", width, height);
+    out += &format!(
+        "<text x=\"{}\" y=\"22\" font-size=\"16\">{}</text>\n",
+        LEFT,
+        heatmap_title(tables),
+    );
+
+    for (j, row) in dcs.iter().enumerate() {
+        out += &format!(
+            "<text x=\"{:.2}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            LEFT + (j as f64 + 0.5) * CELL_WIDTH,
+            TOP - 6.0,
+            row.dc,
+        );
+    }
+
+    for (i, (label, probabilities)) in rows.iter().enumerate() {
+        let y = TOP + i as f64 * CELL_HEIGHT;
+        out += &format!(
+            "<text x=\"{}\" y=\"{:.2}\" text-anchor=\"end\" dominant-baseline=\"middle\">{}</text>\n",
+            LEFT - 6.0,
+            y + CELL_HEIGHT / 2.0,
+            label,
+        );
+        for (j, (dc, p)) in probabilities.iter().enumerate() {
+            out += &format!(
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{}\" height=\"{}\" fill=\"{}\">\
+                 <title>{}, DC {}: {}</title></rect>\n",
+                LEFT + j as f64 * CELL_WIDTH,
+                y,
+                CELL_WIDTH,
+                CELL_HEIGHT,
+                heat(p.to_f64()),
+                label,
+                dc,
+                crate::format_percent(*p),
+            );
+        }
+    }
+
+    out += "</svg>\n";
+    out
+}
+
+/// Renders the given tables as a standalone HTML page containing a heatmap, with one column per
+/// DC and one row per die and turbo token count. Each cell shows its probability of success as a
+/// whole percentage, and is colored from red to green by it.
+///
+/// All tables are expected to contain the same dice and DCs.
+pub fn heatmap_html<P: Probability, D: ExplodingDie>(tables: &[Table<P, D>]) -> String {
+    let title = heatmap_title(tables);
+    let mut out = format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{}</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; }}\n\
+         table {{ border-collapse: collapse; font-size: 9px; }}\n\
+         th, td {{ padding: 2px; text-align: center; }}\n\
+         th {{ font-weight: normal; }}\n\
+         tbody th {{ text-align: right; white-space: nowrap; }}\n\
+         @media print {{ * {{ -webkit-print-color-adjust: exact; print-color-adjust: exact; }} }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <h1>{}</h1>\n\
+         <table>\n\
+         <thead>\n<tr><th>DC</th>",
+        title, title,
+    );
+    for row in tables.first().map(|table| table.rows.as_slice()).unwrap_or_default() {
+        out += &format!("<th>{}</th>", row.dc);
+    }
+    out += "</tr>\n</thead>\n<tbody>\n";
+
+    for (label, probabilities) in heatmap_rows(tables) {
+        out += &format!("<tr><th>{}</th>", label);
+        for (dc, p) in probabilities {
+            out += &format!(
+                "<td style=\"background: {}\" title=\"{}, DC {}: {}\">{:.0}</td>",
+                heat(p.to_f64()),
+                label,
+                dc,
+                crate::format_percent(p),
+                p.to_f64() * 100.0,
+            );
+        }
+        out += "</tr>\n";
+    }
+
+    out += "</tbody>\n</table>\n</body>\n</html>\n";
+    out
+}
+
+/// Returns the rows of a heatmap of the given tables, grouped by die and then by turbo tokens.
+/// Each row has a label naming its die and turbo tokens, and the DC and probability of each cell.
+fn heatmap_rows<P, D: ExplodingDie>(tables: &[Table<P, D>]) -> Vec<(String, Vec<(u32, &P)>)> {
+    let dice = tables.first().map(|table| table.dice.as_slice()).unwrap_or_default();
+    dice.iter()
+        .enumerate()
+        .flat_map(|(i, die)| {
+            tables.iter().map(move |table| {
+                let label = format!("{}, {} {}", die, table.turbo_tokens, tokens(table.turbo_tokens));
+                let cells = table.rows.iter().map(|row| (row.dc, &row.probabilities[i])).collect();
+                (label, cells)
+            })
+        })
+        .collect()
+}
+
+/// Returns the title of a heatmap of the given tables, naming any modifier or advantage.
+fn heatmap_title<P, D>(tables: &[Table<P, D>]) -> String {
+    let conditions = tables.first().map(conditions).unwrap_or_default();
+    format!("Probability of success{}", conditions)
+}
+
+/// Returns the color of a heatmap cell with the given probability, from red at 0% through yellow
+/// to green at 100%.
+fn heat(p: f64) -> String {
+    format!("hsl({:.0}, 75%, 65%)", p.clamp(0.0, 1.0) * 120.0)
+}
+
+/// Returns a title for a table, naming its number of turbo tokens along with any modifier or
+/// advantage.
+fn title<P, D>(table: &Table<P, D>) -> String {
+    format!("{}{}", turbo_tokens(table.turbo_tokens), conditions(table))
+}
+
+/// Describes a number of turbo tokens, such as `1 turbo token` or `3 turbo tokens`.
+pub fn turbo_tokens(count: u32) -> String {
+    format!("{} turbo {}", count, tokens(count))
+}
+
+/// Returns the word for the given number of tokens, pluralised unless there is exactly one.
+fn tokens(count: u32) -> &'static str {
+    if count == 1 { "token" } else { "tokens" }
+}
+
+/// Describes any modifier or advantage applied to the rolls in a table, as a string to append to
+/// its title.
+fn conditions<P, D>(table: &Table<P, D>) -> String {
+    let modifier = match table.modifier {
+        Modifier { value: 0, .. } => String::new(),
+        modifier @ Modifier { rule: ModifierRule::TotalOnly, .. } => {
+            format!(", {} to the total", modifier)
+        },
+        modifier @ Modifier { rule: ModifierRule::CountsTowardExplosion, .. } => {
+            format!(", {} toward explosions", modifier)
+        },
+    };
+    let mode = match table.mode {
+        RollMode::Normal => String::new(),
+        mode => format!(", with {}", mode),
+    };
+    modifier + &mode
+}
+
+/// The script of the page rendered by [`html`], which draws the table of the selected number of
+/// turbo tokens from the embedded data, filtered by the DC search box and sorted by the clicked
+/// column.
+const HTML_SCRIPT: &str = r#"
+const select = document.getElementById("tokens");
+const search = document.getElementById("search");
+const table = document.getElementById("table");
+let sort = { column: 0, descending: false };
+
+tables.forEach((t, i) => {
+  const option = document.createElement("option");
+  option.value = i;
+  option.textContent = t.turbo_tokens + (t.turbo_tokens === 1 ? " turbo token" : " turbo tokens");
+  select.appendChild(option);
+});
+
+// Matches a DC against the search box, which holds a DC such as `12` or a range such as `10-20`.
+// As on the command line, `10..=20` includes 20 and `10..20` does not.
+function matches(dc, query) {
+  query = query.trim();
+  if (query === "") return true;
+  const range = query.match(/^(\d+)\s*(-|\.\.=|\.\.)\s*(\d+)$/);
+  if (range) {
+    const end = range[2] === ".." ? Number(range[3]) - 1 : Number(range[3]);
+    return dc >= Number(range[1]) && dc <= end;
+  }
+  return String(dc) === query;
+}
+
+function render() {
+  const t = tables[select.value];
+  const value = (row) => sort.column === 0 ? row.dc : row.probabilities[sort.column - 1];
+  const rows = t.rows
+    .filter((row) => matches(row.dc, search.value))
+    .sort((a, b) => (value(a) - value(b)) * (sort.descending ? -1 : 1));
+
+  const head = document.createElement("tr");
+  ["DC", ...t.dice].forEach((name, column) => {
+    const th = document.createElement("th");
+    const arrow = sort.column === column ? (sort.descending ? " ▼" : " ▲") : "";
+    th.textContent = name + arrow;
+    th.onclick = () => {
+      sort = { column, descending: sort.column === column && !sort.descending };
+      render();
+    };
+    head.appendChild(th);
+  });
+
+  const body = document.createElement("tbody");
+  rows.forEach((row) => {
+    const tr = document.createElement("tr");
+    [String(row.dc), ...row.probabilities.map((p) => (p * 100).toFixed(6) + "%")].forEach((text) => {
+      const td = document.createElement("td");
+      td.textContent = text;
+      tr.appendChild(td);
+    });
+    body.appendChild(tr);
+  });
+
+  const thead = document.createElement("thead");
+  thead.appendChild(head);
+  table.replaceChildren(thead, body);
+}
+
+select.onchange = render;
+search.oninput = render;
+render();
+"#;
+
+/// Renders the given tables as a single self-contained HTML page, with a selector for the number
+/// of turbo tokens, a search box for DCs, and columns that can be sorted by clicking their
+/// headers.
+///
+/// The tables are embedded in the page as JSON in the same form as [`json()`], so the page works
+/// offline.
+pub fn html<P: Probability, D: ExplodingDie>(tables: &[Table<P, D>]) -> String {
+    // Escape `</` so that the data cannot close the script element early.
+    let data = json(tables, |p| json!(p.to_f64())).to_string().replace("</", "<\\/");
+    let conditions = tables.first().map(conditions).unwrap_or_default();
+    let title = format!("Never Stop Blowing Up probabilities{}", conditions);
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; margin: 2em; }}\n\
+         label {{ margin-right: 1em; }}\n\
+         table {{ border-collapse: collapse; margin-top: 1em; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}\n\
+         th {{ background: #eee; cursor: pointer; user-select: none; }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <h1>{title}</h1>\n\
+         <label>Turbo tokens <select id=\"tokens\"></select></label>\n\
+         <label>DC <input id=\"search\" type=\"search\" placeholder=\"e.g. 12 or 10-20\"></label>\n\
+         <table id=\"table\"></table>\n\
+         <script>\n\
+         const tables = {data};\n\
+         {script}\
+         </script>\n\
+         </body>\n\
+         </html>\n",
+        title = title,
+        data = data,
+        script = HTML_SCRIPT.trim_start(),
+    )
+}
+
+/// The LaTeX environment that [`latex`] typesets each table in.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum LatexEnvironment {
+    /// A `tabular`, which cannot break across pages.
+    Tabular,
+
+    /// A `longtable`, which breaks across pages and repeats its header on each page. Requires the
+    /// `longtable` package.
+    #[default]
+    Longtable,
+}
+
+/// Renders the given tables in LaTeX, each in the given environment and preceded by an unnumbered
+/// subsection naming its number of turbo tokens along with any modifier or advantage. Each
+/// probability is formatted with the given function, and any characters special to LaTeX in it are
+/// escaped.
+///
+/// The tables use the rules from the `booktabs` package.
+pub fn latex<P: Probability, D: ExplodingDie>(
+    tables: &[Table<P, D>],
+    format: impl Fn(&P) -> String,
+    environment: LatexEnvironment,
+) -> String {
+    let name = match environment {
+        LatexEnvironment::Tabular => "tabular",
+        LatexEnvironment::Longtable => "longtable",
+    };
+
+    let mut out = String::new();
+    for (i, table) in tables.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out += &format!("\\subsection*{{{}}}\n\n", escape_latex(&title(table)));
+        out += &format!("\\begin{{{}}}{{r{}}}\n", name, "r".repeat(table.dice.len()));
+
+        let header = std::iter::once("DC".to_string())
+            .chain(table.dice.iter().map(|die| escape_latex(&die.to_string())))
+            .collect::<Vec<_>>()
+            .join(" & ");
+        out += &format!("\\toprule\n{} \\\\\n\\midrule\n", header);
+        if environment == LatexEnvironment::Longtable {
+            out += "\\endhead\n\\bottomrule\n\\endlastfoot\n";
+        }
+
+        for row in &table.rows {
+            let record = std::iter::once(row.dc.to_string())
+                .chain(row.probabilities.iter().map(|p| escape_latex(&format(p))))
+                .collect::<Vec<_>>()
+                .join(" & ");
+            out += &format!("{} \\\\\n", record);
+        }
+
+        if environment == LatexEnvironment::Tabular {
+            out += "\\bottomrule\n";
+        }
+        out += &format!("\\end{{{}}}\n", name);
+    }
+    out
+}
+
+/// Escapes the characters in a string that are special to LaTeX.
+fn escape_latex(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '%' | '&' | '#' | '$' | '_' | '{' | '}' => format!("\\{}", c),
+            '~' => "\\textasciitilde{}".to_string(),
+            '^' => "\\textasciicircum{}".to_string(),
+            '\\' => "\\textbackslash{}".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,254 @@
+use crate::{Die, ExplodingDie};
+use std::collections::HashMap;
+
+/// A single ability check: a die to roll and a difficulty class to beat.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Check<D = Die> {
+    /// The type of die being rolled.
+    pub die: D,
+
+    /// The difficulty class to beat.
+    pub dc: u32,
+}
+
+impl<D: ExplodingDie> std::fmt::Display for Check<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.die, self.dc)
+    }
+}
+
+impl<D> std::str::FromStr for Check<D>
+where
+    D: std::str::FromStr,
+    D::Err: std::fmt::Display,
+{
+    type Err = String;
+
+    /// Parses a check of the form `die:dc`, such as `d8:12`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (die, dc) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid check `{}`, expected `die:dc`", s))?;
+        Ok(Check {
+            die: die.parse().map_err(|e| format!("{}", e))?,
+            dc: dc.trim().parse().map_err(|e| format!("invalid DC `{}`: {}", dc, e))?,
+        })
+    }
+}
+
+/// What a player does after seeing the result of a single die.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Keep the roll as it is. If the die rolled its maximum value, it explodes on its own.
+    Keep,
+
+    /// Spend the given number of turbo tokens on the roll, either to beat the DC or to explode the
+    /// die.
+    Spend(u32),
+}
+
+/// Finds the optimal way to spend turbo tokens over a sequence of checks.
+///
+/// Turbo tokens are a resource carried between checks: spending them now makes the current check
+/// more likely to succeed, but leaves fewer for later checks. The solver treats this as a Markov
+/// decision process, where after each die is rolled, the player decides whether to spend tokens on
+/// it. The player is rewarded 1 for each successful check, plus `token_value` for each token left
+/// over after the last check. As in the rules, a failed check grants the player a turbo token.
+///
+/// With no future checks and a `token_value` of 0, the optimal policy is to always spend tokens if
+/// necessary, matching
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens).
+#[derive(Debug, Clone)]
+pub struct Solver<D = Die> {
+    /// The checks to make, in order. The first check is the current one.
+    checks: Vec<Check<D>>,
+
+    /// The value of each token left over after the last check.
+    token_value: f64,
+
+    /// Memoized expected rewards, keyed by the index of the check, the die being rolled, the
+    /// number of tokens held, and the remaining DC.
+    values: HashMap<(usize, D, u32, u32), f64>,
+
+    /// Memoized probabilities of succeeding at a check when following the optimal policy, keyed
+    /// in the same way as `values`.
+    successes: HashMap<(usize, D, u32, u32), f64>,
+}
+
+impl<D: ExplodingDie> Solver<D> {
+    /// Creates a solver for the given checks, where the first check is the current one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `checks` is empty.
+    pub fn new(checks: Vec<Check<D>>, token_value: f64) -> Self {
+        assert!(!checks.is_empty(), "at least one check is required");
+        Solver {
+            checks,
+            token_value,
+            values: HashMap::new(),
+            successes: HashMap::new(),
+        }
+    }
+
+    /// Returns the checks being solved for.
+    pub fn checks(&self) -> &[Check<D>] {
+        &self.checks
+    }
+
+    /// Returns the expected reward of making all checks starting from the given check, when
+    /// holding the given number of turbo tokens and following the optimal policy.
+    ///
+    /// A `check` index past the last check returns the value of the held tokens.
+    pub fn expected_value(&mut self, check: usize, turbo_tokens: u32) -> f64 {
+        match self.checks.get(check) {
+            Some(&Check { die, dc }) => self.value(check, die, turbo_tokens, dc),
+            None => self.token_value * turbo_tokens as f64,
+        }
+    }
+
+    /// Returns the probability that the current check succeeds when holding the given number of
+    /// turbo tokens and following the optimal policy.
+    pub fn success_probability(&mut self, turbo_tokens: u32) -> f64 {
+        let Check { die, dc } = self.checks[0];
+        self.success(0, die, turbo_tokens, dc)
+    }
+
+    /// Returns the optimal action to take in the middle of the given check, after rolling `roll` on
+    /// `die` while holding the given number of turbo tokens, with `dc` left to beat.
+    ///
+    /// When rolling the first die of a check, `dc` is the DC of the check. After the die explodes,
+    /// `dc` is reduced by the total rolled so far.
+    pub fn action(&mut self, check: usize, die: D, turbo_tokens: u32, dc: u32, roll: u32) -> Action {
+        self.best(check, die, turbo_tokens, dc, roll).0
+    }
+
+    /// Returns the expected reward of rolling `die` in the middle of the given check.
+    fn value(&mut self, check: usize, die: D, turbo_tokens: u32, dc: u32) -> f64 {
+        if dc <= 1 {
+            return 1.0 + self.expected_value(check + 1, turbo_tokens);
+        }
+
+        let key = (check, die, turbo_tokens, dc);
+        if let Some(&value) = self.values.get(&key) {
+            return value;
+        }
+
+        let value = (1..=die.sides())
+            .map(|roll| self.best(check, die, turbo_tokens, dc, roll).1)
+            .sum::<f64>()
+            / die.sides() as f64;
+        self.values.insert(key, value);
+        value
+    }
+
+    /// Returns the probability of succeeding at the given check when rolling `die` in the middle of
+    /// it and following the optimal policy.
+    fn success(&mut self, check: usize, die: D, turbo_tokens: u32, dc: u32) -> f64 {
+        if dc <= 1 {
+            return 1.0;
+        }
+
+        let key = (check, die, turbo_tokens, dc);
+        if let Some(&p) = self.successes.get(&key) {
+            return p;
+        }
+
+        let p = (1..=die.sides())
+            .map(|roll| match (self.best(check, die, turbo_tokens, dc, roll).0, die.explode()) {
+                (Action::Keep, _) if roll >= dc => 1.0,
+                (Action::Keep, Some(next)) if roll == die.sides() => {
+                    self.success(check, next, turbo_tokens, dc - die.sides())
+                },
+                (Action::Keep, _) => 0.0,
+                (Action::Spend(spent), Some(next)) if dc > die.sides() => {
+                    self.success(check, next, turbo_tokens - spent, dc - die.sides())
+                },
+                (Action::Spend(_), _) => 1.0,
+            })
+            .sum::<f64>()
+            / die.sides() as f64;
+        self.successes.insert(key, p);
+        p
+    }
+
+    /// Returns the best action to take after rolling `roll` on `die`, along with its expected
+    /// reward. Ties are broken in favor of spending fewer tokens.
+    fn best(&mut self, check: usize, die: D, turbo_tokens: u32, dc: u32, roll: u32) -> (Action, f64) {
+        // The roll beats the DC on its own.
+        if roll >= dc {
+            return (Action::Keep, 1.0 + self.expected_value(check + 1, turbo_tokens));
+        }
+
+        // The die explodes on its own. Tokens can still be spent on the next die.
+        let next = die.explode();
+        if let Some(next) = next.filter(|_| roll == die.sides()) {
+            return (Action::Keep, self.value(check, next, turbo_tokens, dc - die.sides()));
+        }
+
+        // Otherwise, the player can accept failure and receive a turbo token...
+        let mut best = (Action::Keep, self.expected_value(check + 1, turbo_tokens + 1));
+
+        let spend = match next {
+            // ...or spend tokens to explode the die, if the DC is out of reach without exploding...
+            Some(next) if dc > die.sides() => {
+                let needed = die.sides() - roll;
+                (needed <= turbo_tokens).then(|| {
+                    (needed, self.value(check, next, turbo_tokens - needed, dc - die.sides()))
+                })
+            },
+            // ...or spend tokens to reach the DC.
+            _ => {
+                let needed = dc - roll;
+                (needed <= turbo_tokens)
+                    .then(|| (needed, 1.0 + self.expected_value(check + 1, turbo_tokens - needed)))
+            },
+        };
+
+        if let Some((needed, value)) = spend {
+            if value > best.1 {
+                best = (Action::Spend(needed), value);
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        probability_of_success_with_turbo_tokens,
+        testing::{sweep, Case},
+    };
+
+    #[test]
+    fn matches_always_spending_without_future_checks() {
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            let mut solver = Solver::new(vec![Check { die, dc }], 0.0);
+            let expected = probability_of_success_with_turbo_tokens(die, turbo_tokens, dc);
+            let success = solver.success_probability(turbo_tokens);
+            assert!((success - expected).abs() <= 1e-12, "{}", case);
+            let value = solver.expected_value(0, turbo_tokens);
+            assert!((value - expected).abs() <= 1e-12, "{}", case);
+        }
+    }
+
+    #[test]
+    fn token_value_can_favor_keeping() {
+        let checks = vec![Check { die: Die::D8, dc: 12 }, Check { die: Die::D6, dc: 10 }];
+
+        // Rolling 6 on the d8 fails unless 2 tokens explode it into a d10, which then needs 4 or
+        // more with no tokens left: 7 in 10. Keeping the 6 fails, leaving 3 tokens for the d6.
+        let mut solver = Solver::new(checks.clone(), 0.3);
+        let keep = solver.expected_value(1, 3);
+        let spend = 0.7 * (1.0 + solver.expected_value(1, 0)) + 0.3 * solver.expected_value(1, 1);
+        assert!(keep > spend);
+        assert_eq!(solver.action(0, Die::D8, 2, 12, 6), Action::Keep);
+
+        // Without any value on leftover tokens, exploding is worth it.
+        let mut solver = Solver::new(checks, 0.0);
+        assert_eq!(solver.action(0, Die::D8, 2, 12, 6), Action::Spend(2));
+    }
+}
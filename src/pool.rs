@@ -0,0 +1,199 @@
+use crate::{economy::Outcomes, Check, Die, ExplodingDie, Probability};
+
+/// The best way to share a pool of turbo tokens between several players making checks one after
+/// another.
+///
+/// Before each check, the party gives the player making it some of the tokens in the pool. The
+/// player spends them as described in
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens),
+/// and returns whatever they did not spend to the pool. A player who fails also adds the token
+/// granted for failing to the pool. How many tokens to give therefore depends on how the earlier
+/// checks went, so the allocation is a policy over the number of tokens in the pool before each
+/// check.
+#[derive(Debug, Clone)]
+pub struct Allocation<P = f64, D = Die> {
+    /// The checks made by the players, in order.
+    pub checks: Vec<Check<D>>,
+
+    /// The number of turbo tokens in the pool before the first check.
+    pub pool: u32,
+
+    /// The number of turbo tokens to give to each check, indexed by check and then by the number
+    /// of tokens in the pool before it. Each earlier failure adds a token to the pool, so there
+    /// can be up to `pool + i` tokens in the pool before check `i`.
+    pub turbo_tokens: Vec<Vec<u32>>,
+
+    /// The probability of holding each number of turbo tokens in the pool before each check,
+    /// indexed in the same way as [`Allocation::turbo_tokens`]. The last entry holds the
+    /// probabilities after the last check.
+    pub pools: Vec<Vec<P>>,
+
+    /// The probability of each check succeeding, in the same order as [`Allocation::checks`].
+    pub probabilities: Vec<P>,
+}
+
+impl<P: Probability, D: ExplodingDie> Allocation<P, D> {
+    /// Returns the expected number of successful checks.
+    pub fn expected_successes(&self) -> P {
+        self.probabilities.iter().cloned().sum()
+    }
+
+    /// Returns the expected number of turbo tokens in the pool before the given check, or after
+    /// the last check if `check` is the number of checks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `check` is greater than the number of checks.
+    pub fn expected_pool(&self, check: usize) -> P {
+        self.pools[check]
+            .iter()
+            .enumerate()
+            .map(|(tokens, p)| P::ratio(tokens as u32, 1) * p.clone())
+            .sum()
+    }
+}
+
+/// Finds the way of sharing a pool of turbo tokens between several players making checks one
+/// after another that maximizes the expected number of successful checks, as described in
+/// [`Allocation`].
+///
+/// Ties are broken in favor of giving out fewer tokens.
+///
+/// # Arguments
+///
+/// * `checks` - The checks made by the players, in order.
+/// * `pool` - The number of turbo tokens in the pool before the first check.
+pub fn allocate<D: ExplodingDie>(checks: &[Check<D>], pool: u32) -> Allocation<f64, D> {
+    allocate_in(checks, pool)
+}
+
+/// Same as [`allocate`], but carries out the calculation in the given number type.
+pub fn allocate_in<P: Probability, D: ExplodingDie>(
+    checks: &[Check<D>],
+    pool: u32,
+) -> Allocation<P, D> {
+    let mut outcomes = Outcomes::<P, D>::default();
+    let n = checks.len();
+
+    // Working backwards from the last check, `later[r]` holds the highest expected number of
+    // successes of the checks after the current one when there are `r` tokens in the pool after
+    // it. Each choice of tokens to give leads to the pool left after the check, which is the tokens
+    // not given plus the tokens the player returns.
+    let mut later = vec![P::zero(); pool as usize + n + 1];
+    let mut turbo_tokens = vec![Vec::new(); n];
+    for (i, check) in checks.iter().enumerate().rev() {
+        let mut values = Vec::with_capacity(pool as usize + i + 1);
+        for held in 0..=pool + i as u32 {
+            let mut value = |given: u32| {
+                let outcome = outcomes.get(check.die, given, check.dc);
+                let kept = (held - given) as usize;
+                let successes = outcome.successes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(left, p)| p * (P::one() + later[kept + left].clone()));
+                let failures = outcome.failures
+                    .into_iter()
+                    .enumerate()
+                    .map(|(left, p)| p * later[kept + left + 1].clone());
+                successes.chain(failures).sum::<P>()
+            };
+
+            let mut choice = (value(0), 0);
+            for given in 1..=held {
+                let value = value(given);
+                if value > choice.0 {
+                    choice = (value, given);
+                }
+            }
+            values.push(choice.0);
+            turbo_tokens[i].push(choice.1);
+        }
+        later = values;
+    }
+
+    // Follow the policy forwards to find how the pool and the chance of success evolve.
+    let mut pools = vec![vec![P::zero(); pool as usize + 1]];
+    pools[0][pool as usize] = P::one();
+    let mut probabilities = Vec::with_capacity(n);
+    for (i, check) in checks.iter().enumerate() {
+        let mut next = vec![P::zero(); pool as usize + i + 2];
+        let mut success = P::zero();
+        for (held, p) in pools[i].iter().enumerate() {
+            let given = turbo_tokens[i][held];
+            let outcome = outcomes.get(check.die, given, check.dc);
+            let kept = held - given as usize;
+            for (left, q) in outcome.successes.into_iter().enumerate() {
+                success = success + p.clone() * q.clone();
+                next[kept + left] = next[kept + left].clone() + p.clone() * q;
+            }
+            for (left, q) in outcome.failures.into_iter().enumerate() {
+                next[kept + left + 1] = next[kept + left + 1].clone() + p.clone() * q;
+            }
+        }
+        probabilities.push(success);
+        pools.push(next);
+    }
+
+    Allocation {
+        checks: checks.to_vec(),
+        pool,
+        turbo_tokens,
+        pools,
+        probabilities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        probability_of_success_with_turbo_tokens,
+        testing::{sweep, Case},
+        Exact,
+    };
+
+    fn check(die: Die, dc: u32) -> Check {
+        Check { die, dc }
+    }
+
+    #[test]
+    fn single_check_uses_whole_pool() {
+        for case @ Case { die, turbo_tokens, dc } in sweep() {
+            let allocation = allocate(&[check(die, dc)], turbo_tokens);
+            let expected = probability_of_success_with_turbo_tokens(die, turbo_tokens, dc);
+            assert!((allocation.probabilities[0] - expected).abs() <= 1e-12, "{}", case);
+        }
+    }
+
+    #[test]
+    fn pools_are_distributions() {
+        let checks = [check(Die::D4, 12), check(Die::D8, 10), check(Die::D6, 4), check(Die::D4, 7)];
+        for pool in 0..=4 {
+            let allocation = allocate_in::<Exact, _>(&checks, pool);
+            for (i, pools) in allocation.pools.iter().enumerate() {
+                let total = pools.iter().cloned().sum::<Exact>();
+                assert_eq!(total, Exact::one(), "pool of {} before check {}", pool, i);
+            }
+        }
+    }
+
+    #[test]
+    fn holds_back_tokens_for_later_checks() {
+        // With a pool of 1, the first player needs a 4 (or a 3 and the token) to explode their d4,
+        // and then a 6 on the d6 (or a 5 and the token, if they still have it). The second player
+        // beats DC 4 on a d4 with probability (1 + tokens) / 4.
+        //
+        // Holding the token back, the first check succeeds with probability 1/4 * 1/6 = 1/24, and
+        // fails otherwise, adding a token to the pool. The second check then succeeds with
+        // probability 1/24 * 2/4 + 23/24 * 3/4 = 71/96, for 75/96 = 25/32 expected successes.
+        //
+        // Giving the token out, the first check succeeds with probability 1/4 * 2/6 + 1/4 * 1/6 =
+        // 1/8, leaving 0, 1 or 2 tokens with probabilities 1/12, 1/4 and 2/3. The second check
+        // then succeeds with probability 1/12 * 1/4 + 1/4 * 2/4 + 2/3 * 3/4 = 31/48, for only
+        // 37/48 expected successes.
+        let allocation = allocate_in::<Exact, _>(&[check(Die::D4, 10), check(Die::D4, 4)], 1);
+        assert_eq!(allocation.turbo_tokens, vec![vec![0, 0], vec![0, 1, 2]]);
+        assert_eq!(allocation.probabilities, vec![Exact::ratio(1, 24), Exact::ratio(71, 96)]);
+        assert_eq!(allocation.expected_successes(), Exact::ratio(25, 32));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::ExplodingDie;
+use std::{collections::HashMap, fmt::Display, iter::Sum, ops::{Add, Div, Mul, Sub}};
+
+/// A number type that the probability calculations can be carried out in.
+///
+/// This is implemented for [`f64`], which is fast but accumulates rounding error, and for
+/// [`Exact`](crate::Exact), which represents every probability as an exact fraction.
+pub trait Probability:
+    Clone
+    + PartialOrd
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Sum
+{
+    /// Returns the probability of an impossible event.
+    fn zero() -> Self;
+
+    /// Returns the probability of a certain event.
+    fn one() -> Self;
+
+    /// Returns the probability `numerator / denominator`.
+    fn ratio(numerator: u32, denominator: u32) -> Self;
+
+    /// Converts the probability to an [`f64`], rounding if necessary.
+    fn to_f64(&self) -> f64;
+}
+
+impl Probability for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn ratio(numerator: u32, denominator: u32) -> Self {
+        numerator as f64 / denominator as f64
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+}
+
+/// Computes the probability of beating a given difficulty class when starting with the given die
+/// type. Turbo tokens are not considered in this function.
+///
+/// In Never Stop Blowing Up, each player begins with each of their skills at a d4. When making a
+/// check, the player rolls the die associated with the skill. If the result is the maximum value
+/// for the die, the die explodes, and the player upgrades to the next highest die type and rolls
+/// again, adding the result to the maximum of the previous die. This explosion process can
+/// continue up to a d20, at which point the dice cannot explode any further, and the player will
+/// reroll the d20 until they no longer roll the maximum value. Dice following a custom
+/// [`Ladder`](crate::Ladder) can be passed in place of a [`Die`](crate::Die) to use different
+/// explosion rules.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `dc` - The difficulty class to beat.
+pub fn probability_of_success(die: impl ExplodingDie, dc: u32) -> f64 {
+    probability_of_success_in(die, dc)
+}
+
+/// Computes the probability of beating a given difficulty class when starting with the given die
+/// type and using available turbo tokens to explode the die.
+///
+/// When a player fails an ability check, they receieve a turbo token. Turbo tokens can be spent
+/// on any later roll to add 1 to the result of their roll for each token spent. Players can use
+/// this mechanic to explode their die if they have enough turbo tokens to increase their roll to
+/// the max value of the die.
+///
+/// This function assumes that a player will always spend a turbo token to explode the die if they
+/// can.
+/// If the die cannot explode any further, turbo tokens are added to the roll as usual.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `dc` - The difficulty class to beat.
+pub fn probability_of_success_with_turbo_tokens(die: impl ExplodingDie, turbo_tokens: u32, dc: u32) -> f64 {
+    probability_of_success_with_turbo_tokens_in(die, turbo_tokens, dc)
+}
+
+/// Same as [`probability_of_success`], but carries out the calculation in the given number type.
+pub fn probability_of_success_in<P: Probability>(die: impl ExplodingDie, dc: u32) -> P {
+    // Can always roll a 1 or higher.
+    if dc <= 1 {
+        return P::one();
+    }
+
+    // If the DC is lower than or equal to the maximum value of the die.
+    if dc <= die.sides() {
+        return P::ratio(
+            die.sides() - dc + 1, // # of successful outcomes
+            die.sides(), // # of total outcomes
+        );
+    }
+
+    // If the DC is higher than the maximum value of the die, explode the die and recurse.
+    let Some(next) = die.explode() else {
+        // The die cannot explode, so the DC is out of reach.
+        return P::zero();
+    };
+    let p = P::ratio(1, die.sides()); // Probability of exploding.
+    p * probability_of_success_in(next, dc - die.sides())
+}
+
+/// Same as [`probability_of_success_with_turbo_tokens`], but carries out the calculation in the
+/// given number type.
+pub fn probability_of_success_with_turbo_tokens_in<P: Probability>(
+    die: impl ExplodingDie,
+    turbo_tokens: u32,
+    dc: u32,
+) -> P {
+    with_turbo_tokens(die, turbo_tokens, dc, &mut HashMap::new())
+}
+
+/// Computes the probability described in [`probability_of_success_with_turbo_tokens`], remembering
+/// the probability of every (die, turbo tokens, DC) it reaches in `memo`. Many rolls lead to the
+/// same remaining die, tokens and DC, so without the memo the recursion grows exponentially with
+/// the DC and the number of turbo tokens.
+pub(crate) fn with_turbo_tokens<P: Probability, D: ExplodingDie>(
+    die: D,
+    turbo_tokens: u32,
+    dc: u32,
+    memo: &mut HashMap<(D, u32, u32), P>,
+) -> P {
+    // Can always roll a 1 or higher.
+    if dc <= 1 {
+        return P::one();
+    }
+
+    // If the DC is lower than or equal to the maximum value of the die.
+    if dc <= die.sides() {
+        // Turbo token can be counted as a successful outcome.
+        return P::ratio(
+            (die.sides() - dc + 1 + turbo_tokens).min(die.sides()), // # of successful outcomes
+            die.sides(), // # of total outcomes
+        );
+    }
+
+    // If the die cannot explode, turbo tokens can still be added to the roll to beat the DC.
+    let Some(next) = die.explode() else {
+        return P::ratio(
+            (die.sides() + 1 + turbo_tokens).saturating_sub(dc).min(die.sides()), // # of successful outcomes
+            die.sides(), // # of total outcomes
+        );
+    };
+
+    let key = (die, turbo_tokens, dc);
+    if let Some(p) = memo.get(&key) {
+        return p.clone();
+    }
+
+    // If the DC is higher than the maximum value of the die, explode the die and recurse.
+    let p = (1..=die.sides())
+        .map(|roll| { // Consider all possible rolls with the current die.
+            if roll + turbo_tokens < die.sides() {
+                // The die cannot explode, even with using all turbo tokens.
+                return P::zero();
+            }
+
+            // Die will explode (it must for a chance to beat the DC).
+            let tokens_needed_to_explode = die.sides() - roll;
+            with_turbo_tokens::<P, D>(next, turbo_tokens - tokens_needed_to_explode, dc - die.sides(), memo)
+                / P::ratio(die.sides(), 1)
+        })
+        .sum::<P>();
+    memo.insert(key, p.clone());
+    p
+}
+
+/// Computes the highest difficulty class that can still be beaten with at least the given
+/// probability, when starting with the given die type and number of turbo tokens.
+///
+/// Since the probability of success can only decrease as the DC increases, every DC up to and
+/// including the returned DC is beaten with at least the given probability.
+///
+/// Returns [`None`] if `probability` is not in the range `(0, 1]`, as either no DC (for
+/// probabilities above 1) or every DC (for probabilities of 0 or lower) meets it.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `probability` - The minimum probability of success.
+pub fn highest_dc_with_probability(die: impl ExplodingDie, turbo_tokens: u32, probability: f64) -> Option<u32> {
+    if !(probability > 0.0 && probability <= 1.0) {
+        return None;
+    }
+
+    // A DC of 1 is always beaten, so start searching from 2. Every DC shares the same memo, since
+    // each one recurses through the DCs below it.
+    let mut memo = HashMap::new();
+    (2..)
+        .find(|&dc| with_turbo_tokens::<f64, _>(die, turbo_tokens, dc, &mut memo) < probability)
+        .map(|dc| dc - 1)
+}
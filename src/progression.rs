@@ -0,0 +1,139 @@
+use crate::{Die, ExplodingDie, Probability, ProbabilityCube};
+
+/// When a skill's die permanently advances to the next die on the ladder.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Advancement {
+    /// The skill advances whenever its die explodes during a check, whether on its own or by
+    /// spending turbo tokens.
+    OnExplosion,
+
+    /// The skill advances whenever a check with it succeeds.
+    OnSuccess,
+}
+
+/// How a skill's die advances over a campaign of checks, and how often those checks succeed.
+///
+/// Before each check, the skill is at one of the dice that its starting die can explode into,
+/// with some probability. Making the check with that die either advances the skill to the die it
+/// explodes into, as decided by the [`Advancement`] rule, or leaves it where it is. A die that
+/// cannot explode, such as the top of a capped ladder, never advances.
+///
+/// Turbo tokens are spent as described in
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens),
+/// and the player is assumed to hold the same number of them at every check.
+#[derive(Debug, Clone)]
+pub struct Progression<P = f64, D = Die> {
+    /// The dice the skill can advance through, in order, starting with its starting die.
+    pub dice: Vec<D>,
+
+    /// The probability of the skill being at each die before each check, in the same order as
+    /// [`Progression::dice`]. The last entry is the distribution after the final check.
+    pub distributions: Vec<Vec<P>>,
+
+    /// The probability of succeeding at each check.
+    pub success_probabilities: Vec<P>,
+}
+
+impl<P: Probability, D: ExplodingDie> Progression<P, D> {
+    /// Computes the progression of a skill over a campaign.
+    ///
+    /// # Arguments
+    ///
+    /// * `die` - The skill's die at the start of the campaign.
+    /// * `advancement` - When the skill's die advances.
+    /// * `turbo_tokens` - The number of turbo tokens available to the player at each check.
+    /// * `dcs` - The difficulty class of each check in the campaign, in order.
+    pub fn new(die: D, advancement: Advancement, turbo_tokens: u32, dcs: &[u32]) -> Self {
+        // Advancing follows the same path as exploding, so the dice are the chain of explosions
+        // starting from the starting die, stopping once it repeats or ends.
+        let mut dice = vec![die];
+        while let Some(next) = dice.last().and_then(|die| die.explode()) {
+            if dice.contains(&next) {
+                break;
+            }
+            dice.push(next);
+        }
+
+        let max_dc = dcs.iter().copied().max().unwrap_or(0);
+        let cube = ProbabilityCube::<P, D>::new(&[die], turbo_tokens, max_dc);
+
+        let mut distribution = vec![P::zero(); dice.len()];
+        distribution[0] = P::one();
+        let mut distributions = vec![distribution];
+        let mut success_probabilities = Vec::with_capacity(dcs.len());
+
+        for &dc in dcs {
+            let current = distributions.last().expect("there is always a distribution");
+            let mut next = vec![P::zero(); dice.len()];
+            let mut success = P::zero();
+
+            for (i, &die) in dice.iter().enumerate() {
+                let p = cube.probability(die, turbo_tokens, dc)
+                    .expect("probability should be in the cube")
+                    .clone();
+                let advance = match advancement {
+                    Advancement::OnExplosion => explosion_probability(die, turbo_tokens, dc),
+                    Advancement::OnSuccess => p.clone(),
+                };
+                success = success + current[i].clone() * p;
+
+                // The die that the skill advances to, if it can advance at all.
+                let target = die
+                    .explode()
+                    .and_then(|next| dice.iter().position(|&die| die == next));
+                match target {
+                    Some(j) if j != i => {
+                        next[j] = next[j].clone() + current[i].clone() * advance.clone();
+                        next[i] = next[i].clone() + current[i].clone() * (P::one() - advance);
+                    },
+                    _ => next[i] = next[i].clone() + current[i].clone(),
+                }
+            }
+
+            distributions.push(next);
+            success_probabilities.push(success);
+        }
+
+        Progression {
+            dice,
+            distributions,
+            success_probabilities,
+        }
+    }
+
+    /// Returns the probability of the skill being at each die after the final check, in the same
+    /// order as [`Progression::dice`].
+    pub fn final_distribution(&self) -> &[P] {
+        self.distributions.last().expect("there is always a distribution")
+    }
+
+    /// Returns the expected number of successful checks over the campaign.
+    pub fn expected_successes(&self) -> P {
+        // An empty f64 sum is -0.0, so start from zero explicitly.
+        self.success_probabilities.iter().fold(P::zero(), |sum, p| sum + p.clone())
+    }
+
+    /// Returns the expected fraction of checks that succeed over the campaign, or zero if there
+    /// are no checks.
+    pub fn success_rate(&self) -> P {
+        match self.success_probabilities.len() {
+            0 => P::zero(),
+            checks => self.expected_successes() / P::ratio(checks as u32, 1),
+        }
+    }
+}
+
+/// Returns the probability that the first die rolled in a check explodes, when spending turbo
+/// tokens only if necessary to beat the DC.
+///
+/// A die rolling its maximum value always explodes. If the DC is the maximum value of the die or
+/// higher, turbo tokens spent on the die also explode it, and are spent whenever there are enough
+/// of them.
+fn explosion_probability<P: Probability>(die: impl ExplodingDie, turbo_tokens: u32, dc: u32) -> P {
+    let sides = die.sides();
+    match die.explode() {
+        None => P::zero(),
+        Some(_) if dc >= sides => P::ratio((turbo_tokens + 1).min(sides), sides),
+        Some(_) => P::ratio(1, sides),
+    }
+}
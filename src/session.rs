@@ -0,0 +1,195 @@
+use crate::{roll_with, Ladder, Offer, Roll, Rung};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// An error returned by a [`Session`].
+#[derive(Debug)]
+pub enum SessionError {
+    /// No player with the given name is in the session.
+    UnknownPlayer(String),
+
+    /// A player with the given name is already in the session.
+    DuplicatePlayer(String),
+
+    /// The player does not have a skill with the given name.
+    UnknownSkill { player: String, skill: String },
+
+    /// No die with the given number of sides is on the session's ladder.
+    NotOnLadder(u32),
+
+    /// The session file could not be read or written.
+    Io(std::io::Error),
+
+    /// The session file is not valid.
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SessionError::UnknownPlayer(name) => write!(f, "no player named `{}`", name),
+            SessionError::DuplicatePlayer(name) => {
+                write!(f, "a player named `{}` already exists", name)
+            },
+            SessionError::UnknownSkill { player, skill } => {
+                write!(f, "`{}` has no skill named `{}`", player, skill)
+            },
+            SessionError::NotOnLadder(sides) => {
+                write!(f, "d{} is not on the session's ladder", sides)
+            },
+            SessionError::Io(err) => write!(f, "could not access session file: {}", err),
+            SessionError::Json(err) => write!(f, "invalid session file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<std::io::Error> for SessionError {
+    fn from(err: std::io::Error) -> Self {
+        SessionError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SessionError {
+    fn from(err: serde_json::Error) -> Self {
+        SessionError::Json(err)
+    }
+}
+
+/// A player at the table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Player {
+    /// The number of sides on the die of each of the player's skills, by skill name.
+    pub skills: BTreeMap<String, u32>,
+
+    /// The number of turbo tokens the player holds.
+    pub turbo_tokens: u32,
+}
+
+/// The result of a player making a check with [`Session::check`].
+#[derive(Debug, Clone)]
+pub struct CheckResult<'a> {
+    /// The roll that was made.
+    pub roll: Roll<Rung<'a>>,
+
+    /// True if the roll beat the DC.
+    pub success: bool,
+
+    /// The number of turbo tokens the player holds after the check, including the token awarded
+    /// for failing.
+    pub turbo_tokens: u32,
+}
+
+/// The players at a table, their skill dice, and their turbo tokens, which can be saved to and
+/// loaded from a file between checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// The ladder of dice that the players' skill dice explode through.
+    pub ladder: Ladder,
+
+    /// Each player, by name.
+    pub players: BTreeMap<String, Player>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::new(Ladder::standard())
+    }
+}
+
+impl Session {
+    /// Creates a session with no players, using the given ladder of dice.
+    pub fn new(ladder: Ladder) -> Self {
+        Session {
+            ladder,
+            players: BTreeMap::new(),
+        }
+    }
+
+    /// Loads a session from the given JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SessionError> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Saves the session to the given JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Adds a player with no skills and no turbo tokens.
+    pub fn add_player(&mut self, name: &str) -> Result<(), SessionError> {
+        if self.players.contains_key(name) {
+            return Err(SessionError::DuplicatePlayer(name.to_string()));
+        }
+        self.players.insert(name.to_string(), Player::default());
+        Ok(())
+    }
+
+    /// Removes a player.
+    pub fn remove_player(&mut self, name: &str) -> Result<Player, SessionError> {
+        self.players
+            .remove(name)
+            .ok_or_else(|| SessionError::UnknownPlayer(name.to_string()))
+    }
+
+    /// Returns the player with the given name.
+    pub fn player_mut(&mut self, name: &str) -> Result<&mut Player, SessionError> {
+        self.players
+            .get_mut(name)
+            .ok_or_else(|| SessionError::UnknownPlayer(name.to_string()))
+    }
+
+    /// Sets the die of one of a player's skills, adding the skill if the player does not have it.
+    pub fn set_skill(&mut self, player: &str, skill: &str, sides: u32) -> Result<(), SessionError> {
+        if self.ladder.rung_with_sides(sides).is_none() {
+            return Err(SessionError::NotOnLadder(sides));
+        }
+        self.player_mut(player)?.skills.insert(skill.to_string(), sides);
+        Ok(())
+    }
+
+    /// Has a player make a check with one of their skills against a DC.
+    ///
+    /// The player's turbo tokens are spent as in [`roll_with`], asking `decide` whether to spend
+    /// them whenever they could help. If the check fails, the player is awarded a turbo token.
+    pub fn check(
+        &mut self,
+        player: &str,
+        skill: &str,
+        dc: u32,
+        rng: &mut impl Rng,
+        decide: impl FnMut(&Offer<Rung<'_>>) -> bool,
+    ) -> Result<CheckResult<'_>, SessionError> {
+        let state = self.players
+            .get_mut(player)
+            .ok_or_else(|| SessionError::UnknownPlayer(player.to_string()))?;
+        let &sides = state.skills
+            .get(skill)
+            .ok_or_else(|| SessionError::UnknownSkill {
+                player: player.to_string(),
+                skill: skill.to_string(),
+            })?;
+        let die = self.ladder
+            .rung_with_sides(sides)
+            .ok_or(SessionError::NotOnLadder(sides))?;
+
+        let roll = roll_with(die, state.turbo_tokens, Some(dc), rng, decide);
+        let success = roll.total() >= dc;
+
+        state.turbo_tokens -= roll.tokens_spent();
+        if !success {
+            state.turbo_tokens += 1;
+        }
+
+        Ok(CheckResult {
+            roll,
+            success,
+            turbo_tokens: state.turbo_tokens,
+        })
+    }
+}
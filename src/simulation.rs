@@ -0,0 +1,261 @@
+use crate::{Die, ExplodingDie};
+use rand::Rng;
+
+/// The z-score of a two-sided 95% confidence interval.
+pub const Z_95: f64 = 1.959964;
+
+/// A single die rolled as part of a [`Roll`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Step<D = Die> {
+    /// The type of die that was rolled.
+    pub die: D,
+
+    /// The number shown on the die.
+    pub result: u32,
+
+    /// The number of turbo tokens spent on this die.
+    pub tokens_spent: u32,
+}
+
+impl<D: ExplodingDie> Step<D> {
+    /// Returns the value of the die after spending turbo tokens on it.
+    pub fn value(&self) -> u32 {
+        self.result + self.tokens_spent
+    }
+
+    /// Returns true if the die exploded.
+    pub fn exploded(&self) -> bool {
+        self.value() >= self.die.sides() && self.die.explode().is_some()
+    }
+}
+
+/// The result of physically rolling a die, following every explosion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roll<D = Die> {
+    /// Each die that was rolled, in order.
+    pub steps: Vec<Step<D>>,
+}
+
+impl<D: ExplodingDie> Roll<D> {
+    /// Returns the final total of the roll.
+    pub fn total(&self) -> u32 {
+        self.steps.iter().map(Step::value).sum()
+    }
+
+    /// Returns the total number of turbo tokens spent on the roll.
+    pub fn tokens_spent(&self) -> u32 {
+        self.steps.iter().map(|step| step.tokens_spent).sum()
+    }
+}
+
+impl<D: ExplodingDie> std::fmt::Display for Roll<D> {
+    /// Formats the roll as a log of every die rolled, such as `d4: 4 💥 → d6: 2 (+4) 💥 → d8: 3 = 13`,
+    /// where turbo tokens spent on a die are shown in parentheses.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " → ")?;
+            }
+            write!(f, "{}: {}", step.die, step.result)?;
+            if step.tokens_spent > 0 {
+                write!(f, " (+{})", step.tokens_spent)?;
+            }
+            if step.exploded() {
+                write!(f, " 💥")?;
+            }
+        }
+        write!(f, " = {}", self.total())
+    }
+}
+
+/// Why turbo tokens would be spent on a die.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Purpose {
+    /// Spending the tokens beats the DC.
+    Succeed,
+
+    /// Spending the tokens explodes the die.
+    Explode,
+}
+
+/// An opportunity to spend turbo tokens on a die during a [`Roll`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Offer<D = Die> {
+    /// The type of die that was rolled.
+    pub die: D,
+
+    /// The number shown on the die.
+    pub result: u32,
+
+    /// The total of the roll so far, not including this die.
+    pub total: u32,
+
+    /// The number of turbo tokens that would be spent.
+    pub cost: u32,
+
+    /// The number of turbo tokens held before spending.
+    pub turbo_tokens: u32,
+
+    /// What spending the tokens would achieve.
+    pub purpose: Purpose,
+}
+
+/// Physically rolls the given die with the given number of turbo tokens, exploding it according
+/// to the rules.
+///
+/// If a DC is given, turbo tokens are spent only when necessary to beat the DC, either by adding
+/// them to the roll directly or by exploding the die, as assumed by
+/// [`probability_of_success_with_turbo_tokens`](crate::probability_of_success_with_turbo_tokens).
+/// Without a DC, turbo tokens are spent whenever they can explode the die.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `dc` - The difficulty class to beat, if any.
+/// * `rng` - The source of randomness to roll the dice with.
+pub fn roll<D: ExplodingDie>(
+    die: D,
+    turbo_tokens: u32,
+    dc: Option<u32>,
+    rng: &mut impl Rng,
+) -> Roll<D> {
+    roll_with(die, turbo_tokens, dc, rng, |_| true)
+}
+
+/// Same as [`roll`], but lets the player decide whether to spend turbo tokens.
+///
+/// Whenever [`roll`] would spend turbo tokens, `decide` is called with the offer instead, and the
+/// tokens are only spent if it returns true.
+pub fn roll_with<D: ExplodingDie>(
+    die: D,
+    mut turbo_tokens: u32,
+    dc: Option<u32>,
+    rng: &mut impl Rng,
+    mut decide: impl FnMut(&Offer<D>) -> bool,
+) -> Roll<D> {
+    let mut steps = Vec::new();
+    let mut die = die;
+    let mut total = 0;
+
+    loop {
+        let result = rng.gen_range(1..=die.sides());
+
+        // The DC left to beat after this die, or `None` if there is no DC.
+        let remaining = dc.map(|dc| dc.saturating_sub(total));
+
+        // Whether this die can explode, and whether a DC can only be beaten by exploding it.
+        let explodes = die.explode().is_some();
+        let must_explode = |remaining: u32| explodes && remaining > die.sides();
+
+        let offer = |cost, purpose| Offer { die, result, total, cost, turbo_tokens, purpose };
+        let offer = match remaining {
+            // The die explodes on its own, or the DC has already been beaten.
+            _ if explodes && result == die.sides() => None,
+            Some(remaining) if result >= remaining => None,
+
+            // Spend tokens to beat the DC directly if it is within reach of this die.
+            Some(remaining) if !must_explode(remaining) => (result + turbo_tokens >= remaining)
+                .then(|| offer(remaining - result, Purpose::Succeed)),
+
+            // Otherwise, spend tokens to explode the die if possible.
+            _ => (explodes && result + turbo_tokens >= die.sides())
+                .then(|| offer(die.sides() - result, Purpose::Explode)),
+        };
+
+        let tokens_spent = match offer {
+            Some(offer) if decide(&offer) => offer.cost,
+            _ => 0,
+        };
+
+        turbo_tokens -= tokens_spent;
+        let step = Step { die, result, tokens_spent };
+        steps.push(step);
+        total += step.value();
+
+        match die.explode() {
+            Some(next) if step.exploded() => die = next,
+            _ => return Roll { steps },
+        }
+    }
+}
+
+/// The result of simulating many rolls against a DC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Estimate {
+    /// The number of rolls simulated.
+    pub trials: u64,
+
+    /// The number of rolls that beat the DC.
+    pub successes: u64,
+}
+
+impl Estimate {
+    /// Returns the estimated probability of success.
+    pub fn probability(&self) -> f64 {
+        self.successes as f64 / self.trials as f64
+    }
+
+    /// Returns the Wilson score interval of the probability of success for the given z-score,
+    /// such as [`Z_95`] for a 95% confidence interval.
+    ///
+    /// Unlike the normal approximation, the Wilson interval remains accurate for probabilities
+    /// close to 0 or 1, which are common at high DCs. When no roll or every roll beats the DC,
+    /// the interval includes exactly 0 or 1 respectively.
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        let n = self.trials as f64;
+        let p = self.probability();
+        let z2 = z * z;
+
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+
+        // The bounds are exactly 0 and 1 at the edges, but rounding can leave them just inside.
+        let low = if self.successes == 0 { 0.0 } else { (center - margin).max(0.0) };
+        let high = if self.successes == self.trials { 1.0 } else { (center + margin).min(1.0) };
+        (low, high)
+    }
+}
+
+/// Estimates the probability of beating a given difficulty class by physically rolling the given
+/// die many times, spending turbo tokens as described in [`roll`].
+///
+/// Seeding `rng` makes the estimate reproducible.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+/// * `dc` - The difficulty class to beat.
+/// * `trials` - The number of rolls to simulate.
+/// * `rng` - The source of randomness to roll the dice with.
+pub fn simulate(
+    die: impl ExplodingDie,
+    turbo_tokens: u32,
+    dc: u32,
+    trials: u64,
+    rng: &mut impl Rng,
+) -> Estimate {
+    let successes = (0..trials)
+        .filter(|_| roll(die, turbo_tokens, Some(dc), rng).total() >= dc)
+        .count() as u64;
+    Estimate { trials, successes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confidence_interval_includes_edges() {
+        let all = Estimate { trials: 100_000, successes: 100_000 };
+        let (low, high) = all.confidence_interval(Z_95);
+        assert!(low < 1.0);
+        assert_eq!(high, 1.0);
+
+        let none = Estimate { trials: 100_000, successes: 0 };
+        let (low, high) = none.confidence_interval(Z_95);
+        assert_eq!(low, 0.0);
+        assert!(high > 0.0);
+    }
+}
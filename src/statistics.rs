@@ -0,0 +1,94 @@
+use crate::{distribution, Distribution, ExplodingDie};
+
+/// The largest probability mass that [`statistics`] allows to be left in the tail of the
+/// distribution it computes statistics from.
+pub const TAIL_TOLERANCE: f64 = 1e-12;
+
+/// Summary statistics of the final total of a roll.
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    /// The expected total.
+    pub mean: f64,
+
+    /// The variance of the total.
+    pub variance: f64,
+
+    /// The standard deviation of the total.
+    pub standard_deviation: f64,
+
+    /// The median total.
+    pub median: u32,
+
+    /// The cumulative probability of each total, where index `i` holds the probability of a total
+    /// of `i + 1` or lower.
+    cumulative: Vec<f64>,
+}
+
+impl Statistics {
+    /// Computes statistics from the given distribution.
+    ///
+    /// Totals in the tail of the distribution are ignored, so the distribution's cutoff should be
+    /// high enough that its tail is negligible.
+    pub fn of(distribution: &Distribution) -> Statistics {
+        let mean = distribution
+            .iter()
+            .map(|(total, p)| total as f64 * p)
+            .sum::<f64>();
+        let variance = distribution
+            .iter()
+            .map(|(total, p)| (total as f64 - mean).powi(2) * p)
+            .sum::<f64>();
+        let cumulative = distribution
+            .iter()
+            .scan(0.0, |acc, (_, p)| {
+                *acc += p;
+                Some(*acc)
+            })
+            .collect::<Vec<_>>();
+
+        let mut statistics = Statistics {
+            mean,
+            variance,
+            standard_deviation: variance.sqrt(),
+            median: 0,
+            cumulative,
+        };
+        statistics.median = statistics.percentile(0.5).unwrap_or(distribution.cutoff());
+        statistics
+    }
+
+    /// Returns the lowest total that the roll is at or below with the given probability, where
+    /// `q` is between 0 and 1. For example, `percentile(0.9)` returns the 90th percentile.
+    ///
+    /// Returns [`None`] if the percentile lies in the ignored tail of the distribution.
+    pub fn percentile(&self, q: f64) -> Option<u32> {
+        // Allow for a small amount of rounding error in the cumulative probabilities.
+        let q = q - f64::EPSILON * 4.0;
+        self.cumulative
+            .iter()
+            .position(|&cumulative| cumulative >= q)
+            .map(|index| index as u32 + 1)
+    }
+}
+
+/// Computes statistics of the final total when starting with the given die type and number of
+/// turbo tokens.
+///
+/// Turbo tokens are spent as described in [`distribution`](crate::distribution). The distribution
+/// is computed with a cutoff high enough to leave at most [`TAIL_TOLERANCE`] probability in its
+/// tail.
+///
+/// # Arguments
+///
+/// * `die` - The type of die being rolled.
+/// * `turbo_tokens` - The number of turbo tokens available to the player.
+pub fn statistics(die: impl ExplodingDie, turbo_tokens: u32) -> Statistics {
+    let mut cutoff = 64;
+    loop {
+        let distribution = distribution(die, turbo_tokens, cutoff);
+        if *distribution.tail() <= TAIL_TOLERANCE {
+            return Statistics::of(&distribution);
+        }
+        cutoff *= 2;
+    }
+}
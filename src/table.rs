@@ -0,0 +1,162 @@
+use crate::{Die, ExplodingDie, Modifier, Probability, ProbabilityCube, RollMode};
+use std::ops::RangeInclusive;
+use tabled::{builder::Builder, settings::style::Style};
+
+/// A table of the probability of beating each DC in a range with each of a set of dice, for a
+/// fixed number of turbo tokens.
+#[derive(Debug, Clone)]
+pub struct Table<P = f64, D = Die> {
+    /// The number of turbo tokens available to the player.
+    pub turbo_tokens: u32,
+
+    /// The modifier applied to every roll in the table.
+    pub modifier: Modifier,
+
+    /// Whether every roll in the table is made with advantage or disadvantage.
+    pub mode: RollMode,
+
+    /// The dice in the table, one per column.
+    pub dice: Vec<D>,
+
+    /// The rows of the table, one per DC.
+    pub rows: Vec<Row<P>>,
+}
+
+/// A single row of a [`Table`].
+#[derive(Debug, Clone)]
+pub struct Row<P = f64> {
+    /// The difficulty class to beat.
+    pub dc: u32,
+
+    /// The probability of beating the DC with each die in the table, in the same order as
+    /// [`Table::dice`].
+    pub probabilities: Vec<P>,
+}
+
+impl<P: Probability, D: ExplodingDie> Table<P, D> {
+    /// Computes the table for the given dice, number of turbo tokens, and range of DCs.
+    pub fn new(dice: &[D], turbo_tokens: u32, dcs: RangeInclusive<u32>) -> Self {
+        Table::with_modifier(dice, turbo_tokens, dcs, Modifier::default())
+    }
+
+    /// Same as [`Table::new`], but with a flat modifier applied to every roll.
+    pub fn with_modifier(
+        dice: &[D],
+        turbo_tokens: u32,
+        dcs: RangeInclusive<u32>,
+        modifier: Modifier,
+    ) -> Self {
+        let max_dc = dcs.end().saturating_add(modifier.value.min(0).unsigned_abs());
+        let cube = ProbabilityCube::new(dice, turbo_tokens, max_dc);
+        Table::from_cube_with_modifier(&cube, dice, turbo_tokens, dcs, modifier)
+    }
+
+    /// Builds the table for the given dice, number of turbo tokens, and range of DCs by looking up
+    /// each probability in an already filled cube. This is faster than [`Table::new`] when building
+    /// many tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the probabilities are not in the cube.
+    pub fn from_cube(
+        cube: &ProbabilityCube<P, D>,
+        dice: &[D],
+        turbo_tokens: u32,
+        dcs: RangeInclusive<u32>,
+    ) -> Self {
+        Table::from_cube_with_modifier(cube, dice, turbo_tokens, dcs, Modifier::default())
+    }
+
+    /// Same as [`Table::from_cube`], but with a flat modifier applied to every roll.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the probabilities depend on entries that are not in the cube, as
+    /// described in [`ProbabilityCube::probability_with_modifier`].
+    pub fn from_cube_with_modifier(
+        cube: &ProbabilityCube<P, D>,
+        dice: &[D],
+        turbo_tokens: u32,
+        dcs: RangeInclusive<u32>,
+        modifier: Modifier,
+    ) -> Self {
+        Table::from_fn(dice, turbo_tokens, dcs, modifier, RollMode::Normal, |die, dc| {
+            cube.probability_with_modifier(die, turbo_tokens, dc, modifier)
+        })
+    }
+
+    /// Builds the table for the given dice, number of turbo tokens, and range of DCs by looking up
+    /// each probability of rolling with advantage or disadvantage in an already filled cube.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the probabilities are not in the cube.
+    pub fn from_cube_with_advantage(
+        cube: &ProbabilityCube<P, D>,
+        dice: &[D],
+        turbo_tokens: u32,
+        dcs: RangeInclusive<u32>,
+        mode: RollMode,
+    ) -> Self {
+        Table::from_fn(dice, turbo_tokens, dcs, Modifier::default(), mode, |die, dc| {
+            cube.probability_with_advantage(die, turbo_tokens, dc, mode)
+        })
+    }
+
+    /// Builds the table by computing the probability of beating each DC with each die with the
+    /// given function.
+    fn from_fn(
+        dice: &[D],
+        turbo_tokens: u32,
+        dcs: RangeInclusive<u32>,
+        modifier: Modifier,
+        mode: RollMode,
+        probability: impl Fn(D, u32) -> Option<P>,
+    ) -> Self {
+        let rows = dcs
+            .map(|dc| Row {
+                dc,
+                probabilities: dice
+                    .iter()
+                    .map(|&die| probability(die, dc).expect("probability should be in the cube"))
+                    .collect(),
+            })
+            .collect();
+
+        Table {
+            turbo_tokens,
+            modifier,
+            mode,
+            dice: dice.to_vec(),
+            rows,
+        }
+    }
+
+    /// Renders the table in markdown, formatting each probability with the given function.
+    pub fn to_markdown(&self, format: impl Fn(&P) -> String) -> String {
+        let mut table = Builder::default();
+
+        let header = std::iter::once("DC".to_string())
+            .chain(self.dice.iter().map(|die| die.to_string()));
+        table.push_record(header);
+
+        for row in &self.rows {
+            let probabilities = row.probabilities.iter().map(&format);
+            table.push_record(std::iter::once(row.dc.to_string()).chain(probabilities));
+        }
+
+        let mut table = table.build();
+        table.with(Style::markdown());
+        table.to_string()
+    }
+}
+
+/// Formats a probability as a percentage, rounded to six decimal places.
+pub fn format_percent<P: Probability>(p: &P) -> String {
+    format!("{:.6}%", p.to_f64() * 100.0)
+}
+
+/// Formats a probability as a fraction if it is exact, or as a decimal otherwise.
+pub fn format_fraction<P: Probability>(p: &P) -> String {
+    p.to_string()
+}
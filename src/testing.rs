@@ -0,0 +1,36 @@
+use crate::Die;
+
+/// The highest number of turbo tokens in a [`sweep`].
+pub(crate) const MAX_TOKENS: u32 = 4;
+
+/// The highest DC in a [`sweep`].
+pub(crate) const MAX_DC: u32 = 70;
+
+/// A single die, number of turbo tokens and DC to check a calculation at.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Case {
+    /// The type of die being rolled.
+    pub(crate) die: Die,
+
+    /// The number of turbo tokens available to the player.
+    pub(crate) turbo_tokens: u32,
+
+    /// The difficulty class to beat.
+    pub(crate) dc: u32,
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} with {} turbo tokens against DC {}", self.die, self.turbo_tokens, self.dc)
+    }
+}
+
+/// Returns every standard die with every number of turbo tokens up to [`MAX_TOKENS`], against every
+/// DC up to [`MAX_DC`].
+pub(crate) fn sweep() -> impl Iterator<Item = Case> {
+    Die::ALL.into_iter().flat_map(|die| {
+        (0..=MAX_TOKENS).flat_map(move |turbo_tokens| {
+            (0..=MAX_DC).map(move |dc| Case { die, turbo_tokens, dc })
+        })
+    })
+}